@@ -12,6 +12,8 @@
 use crate::event::Event;
 use crate::theme;
 use crate::Vec2;
+#[cfg(test)]
+use std::cell::RefCell;
 use unicode_width::UnicodeWidthStr;
 
 /// Trait defining the required methods to be a backend.
@@ -162,3 +164,75 @@ impl Backend for Dummy {
     fn set_effect(&self, _: theme::Effect) {}
     fn unset_effect(&self, _: theme::Effect) {}
 }
+
+/// Backend recording everything printed, with the active effects.
+///
+/// Used to test what views draw.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct Recorder {
+    effects: RefCell<enumset::EnumSet<theme::Effect>>,
+    /// Every printed text, in order.
+    pub prints: RefCell<Vec<(Vec2, String, enumset::EnumSet<theme::Effect>)>>,
+}
+
+#[cfg(test)]
+impl Recorder {
+    /// Returns the content of the line `y`, up to `width` cells.
+    ///
+    /// Later prints replace earlier ones. Empty cells are spaces.
+    pub fn line(&self, y: usize, width: usize) -> String {
+        let mut cells = vec![" ".to_string(); width];
+        for (pos, text, _) in self.prints.borrow().iter() {
+            if pos.y != y {
+                continue;
+            }
+            for (i, c) in text.chars().enumerate() {
+                if let Some(cell) = cells.get_mut(pos.x + i) {
+                    *cell = c.to_string();
+                }
+            }
+        }
+        cells.concat()
+    }
+}
+
+#[cfg(test)]
+impl Backend for Recorder {
+    fn poll_event(&mut self) -> Option<Event> {
+        None
+    }
+
+    fn finish(&mut self) {}
+
+    fn refresh(&mut self) {}
+
+    fn has_colors(&self) -> bool {
+        true
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        (80, 24).into()
+    }
+
+    fn print_at(&self, pos: Vec2, text: &str) {
+        let effects = *self.effects.borrow();
+        self.prints
+            .borrow_mut()
+            .push((pos, text.to_string(), effects));
+    }
+
+    fn clear(&self, _: theme::Color) {}
+
+    fn set_color(&self, colors: theme::ColorPair) -> theme::ColorPair {
+        colors
+    }
+
+    fn set_effect(&self, effect: theme::Effect) {
+        self.effects.borrow_mut().insert(effect);
+    }
+
+    fn unset_effect(&self, effect: theme::Effect) {
+        self.effects.borrow_mut().remove(effect);
+    }
+}
@@ -4,7 +4,7 @@ use crate::backend::Backend;
 use crate::direction::Orientation;
 use crate::rect::Rect;
 use crate::theme::{
-    BorderStyle, ColorStyle, Effect, PaletteColor, Style, Theme,
+    BorderStyle, ColorStyle, ColorType, Effect, PaletteColor, Style, Theme,
 };
 use crate::utils::lines::simple::{prefix, suffix};
 use crate::with::With;
@@ -289,6 +289,11 @@ impl<'a, 'b> Printer<'a, 'b> {
     /// Call the given closure with a colored printer,
     /// that will apply the given color on prints.
     ///
+    /// The effects set in the theme for the palette roles used by `c` are
+    /// also applied (see [`Theme::effects`]).
+    ///
+    /// [`Theme::effects`]: crate::theme::Theme::effects
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -306,8 +311,14 @@ impl<'a, 'b> Printer<'a, 'b> {
     where
         F: FnOnce(&Printer),
     {
+        let role_effects = |color| match color {
+            ColorType::Palette(role) => self.theme.effects[role],
+            ColorType::Color(_) => EnumSet::new(),
+        };
+        let effects = role_effects(c.front) | role_effects(c.back);
+
         let old = self.backend.set_color(c.resolve(&self.theme.palette));
-        f(self);
+        self.with_effects(effects, f);
         self.backend.set_color(old);
     }
 
@@ -334,10 +345,13 @@ impl<'a, 'b> Printer<'a, 'b> {
 
     /// Call the given closure with a modified printer
     /// that will apply the given effect on prints.
+    ///
+    /// Nothing is applied if the theme disables effects.
     pub fn with_effect<F>(&self, effect: Effect, f: F)
     where
         F: FnOnce(&Printer),
     {
+        let effect = self.theme.effective_effect(effect);
         self.backend.set_effect(effect);
         f(self);
         self.backend.unset_effect(effect);
@@ -365,11 +379,15 @@ impl<'a, 'b> Printer<'a, 'b> {
 
     /// Call the given closure with a modified printer
     /// that will apply each given effect on prints.
+    ///
+    /// Effects are applied in the order given by [`Effect::ordered`].
+    ///
+    /// [`Effect::ordered`]: theme/enum.Effect.html#method.ordered
     pub fn with_effects<F>(&self, effects: EnumSet<Effect>, f: F)
     where
         F: FnOnce(&Printer),
     {
-        match effects.iter().next() {
+        match Effect::ordered(effects).next() {
             None => f(self),
             Some(effect) => {
                 let mut effects = effects;
@@ -409,19 +427,28 @@ impl<'a, 'b> Printer<'a, 'b> {
             return;
         }
         let size = size - (1, 1);
+        let chars = self.theme.borders.chars();
 
         self.with_high_border(invert, |s| {
-            s.print(start, "┌");
-            s.print(start + size.keep_y(), "└");
-            s.print_hline(start + (1, 0), size.x - 1, "─");
-            s.print_vline(start + (0, 1), size.y - 1, "│");
+            s.print(start, chars.top_left);
+            s.print(start + size.keep_y(), chars.bottom_left);
+            s.print_hline(start + (1, 0), size.x - 1, chars.horizontal);
+            s.print_vline(start + (0, 1), size.y - 1, chars.vertical);
         });
 
         self.with_low_border(invert, |s| {
-            s.print(start + size.keep_x(), "┐");
-            s.print(start + size, "┘");
-            s.print_hline(start + (1, 0) + size.keep_y(), size.x - 1, "─");
-            s.print_vline(start + (0, 1) + size.keep_x(), size.y - 1, "│");
+            s.print(start + size.keep_x(), chars.top_right);
+            s.print(start + size, chars.bottom_right);
+            s.print_hline(
+                start + (1, 0) + size.keep_y(),
+                size.x - 1,
+                chars.horizontal,
+            );
+            s.print_vline(
+                start + (0, 1) + size.keep_x(),
+                size.y - 1,
+                chars.vertical,
+            );
         });
     }
 
@@ -486,14 +513,22 @@ impl<'a, 'b> Printer<'a, 'b> {
     }
 
     /// Prints a horizontal delimiter with side border `├` and `┤`.
+    ///
+    /// Characters come from the theme's border style, with single lines
+    /// when borders are disabled.
     pub fn print_hdelim<T>(&self, start: T, len: usize)
     where
         T: Into<Vec2>,
     {
         let start = start.into();
-        self.print(start, "├");
-        self.print_hline(start + (1, 0), len.saturating_sub(2), "─");
-        self.print(start + (len.saturating_sub(1), 0), "┤");
+        let chars = self.theme.borders.frame_chars();
+        self.print(start, chars.left_tee);
+        self.print_hline(
+            start + (1, 0),
+            len.saturating_sub(2),
+            chars.horizontal,
+        );
+        self.print(start + (len.saturating_sub(1), 0), chars.right_tee);
     }
 
     /// Returns a sub-printer with the given offset.
@@ -633,3 +668,64 @@ impl<'a, 'b> Printer<'a, 'b> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Recorder;
+    use crate::theme::BorderStyle;
+
+    fn printed_effects(theme: &Theme, style: ColorStyle) -> EnumSet<Effect> {
+        let backend = Recorder::default();
+        let printer = Printer::new((20, 5), theme, &backend);
+        printer.with_color(style, |printer| printer.print((0, 0), "Title"));
+
+        let prints = backend.prints.borrow();
+        assert_eq!(prints.len(), 1);
+        prints[0].2
+    }
+
+    #[test]
+    fn test_role_effects() {
+        let mut theme = Theme::default();
+        theme.effects[PaletteColor::TitlePrimary] =
+            EnumSet::only(Effect::Bold);
+
+        assert_eq!(
+            printed_effects(&theme, ColorStyle::title_primary()),
+            EnumSet::only(Effect::Bold)
+        );
+        assert_eq!(
+            printed_effects(&theme, ColorStyle::primary()),
+            EnumSet::new()
+        );
+
+        // Effects are only applied inside `with_color`.
+        let backend = Recorder::default();
+        let printer = Printer::new((20, 5), &theme, &backend);
+        printer.with_color(ColorStyle::title_primary(), |_| ());
+        printer.print((0, 0), "Plain");
+        assert_eq!(backend.prints.borrow()[0].2, EnumSet::new());
+
+        theme.effects_enabled = false;
+        assert_eq!(
+            printed_effects(&theme, ColorStyle::title_primary()),
+            EnumSet::only(Effect::Simple)
+        );
+    }
+
+    #[test]
+    fn test_print_hdelim() {
+        let mut theme = Theme::default();
+        for &(borders, expected) in &[
+            (BorderStyle::Simple, "├───┤"),
+            (BorderStyle::Double, "╠═══╣"),
+        ] {
+            theme.borders = borders;
+            let backend = Recorder::default();
+            let printer = Printer::new((5, 1), &theme, &backend);
+            printer.print_hdelim((0, 0), 5);
+            assert_eq!(backend.line(0, 5), expected);
+        }
+    }
+}
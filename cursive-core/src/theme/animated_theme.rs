@@ -0,0 +1,115 @@
+use super::{Palette, Theme};
+use std::time::Duration;
+
+/// Transition from one theme to another over time.
+///
+/// Each [`sample`] returns the end theme, with a palette blended from the
+/// start palette (see [`Palette::blend_with`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive_core::theme::{AnimatedTheme, Theme};
+/// # use std::time::Duration;
+/// let animation = AnimatedTheme::new(
+///     &Theme::default(),
+///     Theme::named("solarized").unwrap(),
+///     Duration::from_millis(300),
+/// );
+///
+/// // Call this on every refresh until `is_done` returns `true`.
+/// let theme = animation.sample(Duration::from_millis(100));
+/// ```
+///
+/// [`sample`]: #method.sample
+/// [`Palette::blend_with`]: struct.Palette.html#method.blend_with
+#[derive(Clone, Debug)]
+pub struct AnimatedTheme {
+    start: Palette,
+    end: Theme,
+    duration: Duration,
+}
+
+impl AnimatedTheme {
+    /// Creates a transition from `start` to `end`, lasting `duration`.
+    ///
+    /// Only the palette of `start` is used.
+    pub fn new(start: &Theme, end: Theme, duration: Duration) -> Self {
+        AnimatedTheme {
+            start: start.palette.clone(),
+            end,
+            duration,
+        }
+    }
+
+    /// Returns the theme `elapsed` after the start of the transition.
+    ///
+    /// Past the duration, this returns the end theme.
+    pub fn sample(&self, elapsed: Duration) -> Theme {
+        if self.is_done(elapsed) {
+            return self.end.clone();
+        }
+
+        let t = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        Theme {
+            palette: self.start.blend_with(&self.end.palette, t),
+            ..self.end.clone()
+        }
+    }
+
+    /// Returns `true` if the transition is over after `elapsed`.
+    pub fn is_done(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+
+    /// Returns the duration of the transition.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{Color, PaletteColor};
+
+    #[test]
+    fn test_sample() {
+        let mut start = Theme::default();
+        start.palette[PaletteColor::View] = Color::Rgb(0, 0, 0);
+        let mut end = Theme {
+            shadow: false,
+            ..Theme::default()
+        };
+        end.palette[PaletteColor::View] = Color::Rgb(200, 200, 200);
+
+        let animation =
+            AnimatedTheme::new(&start, end.clone(), Duration::from_secs(2));
+
+        let theme = animation.sample(Duration::from_secs(0));
+        assert_eq!(theme.palette, start.palette);
+        assert!(!theme.shadow);
+
+        let theme = animation.sample(Duration::from_secs(1));
+        assert_eq!(
+            theme.palette[PaletteColor::View],
+            Color::Rgb(100, 100, 100)
+        );
+
+        assert!(!animation.is_done(Duration::from_secs(1)));
+        assert!(animation.is_done(Duration::from_secs(3)));
+        let theme = animation.sample(Duration::from_secs(3));
+        assert_eq!(theme.palette, end.palette);
+    }
+
+    #[test]
+    fn test_zero_duration() {
+        let end = Theme::named("light").unwrap();
+        let animation = AnimatedTheme::new(
+            &Theme::default(),
+            end.clone(),
+            Duration::new(0, 0),
+        );
+        assert_eq!(animation.sample(Duration::new(0, 0)).palette, end.palette);
+    }
+}
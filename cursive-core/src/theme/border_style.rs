@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::fmt;
 
 /// Specifies how some borders should be drawn.
 ///
@@ -11,16 +11,267 @@ pub enum BorderStyle {
     Outset,
     /// No borders.
     None,
+    /// Double-line borders.
+    Double,
+    /// Simple borders with rounded corners.
+    Rounded,
 }
 
-impl<S: Deref<Target = String>> From<S> for BorderStyle {
+impl BorderStyle {
+    /// Parses a border style from its name.
+    ///
+    /// Returns `None` if `s` is not one of `"simple"`, `"outset"`, `"none"`,
+    /// `"double"` or `"rounded"`.
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "simple" => BorderStyle::Simple,
+            "outset" => BorderStyle::Outset,
+            "none" => BorderStyle::None,
+            "double" => BorderStyle::Double,
+            "rounded" => BorderStyle::Rounded,
+            _ => return None,
+        })
+    }
+
+    /// Returns the characters used to draw borders in this style.
+    ///
+    /// `Simple` and `Outset` both use single lines: the 3D effect of
+    /// `Outset` comes from the colors used for each side. `None` uses
+    /// spaces.
+    pub fn chars(self) -> BorderChars {
+        match self {
+            BorderStyle::Simple | BorderStyle::Outset => BorderChars::SINGLE,
+            BorderStyle::None => BorderChars::BLANK,
+            BorderStyle::Double => BorderChars::DOUBLE,
+            BorderStyle::Rounded => BorderChars::ROUNDED,
+        }
+    }
+
+    /// Returns the characters used for lines drawn whatever the style.
+    ///
+    /// Menus and scroll frames always need visible lines, so `None` uses
+    /// single lines here.
+    pub(crate) fn frame_chars(self) -> BorderChars {
+        match self {
+            BorderStyle::None => BorderChars::SINGLE,
+            style => style.chars(),
+        }
+    }
+
+    /// Returns the name of this style, as used in theme files.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BorderStyle::Simple => "simple",
+            BorderStyle::Outset => "outset",
+            BorderStyle::None => "none",
+            BorderStyle::Double => "double",
+            BorderStyle::Rounded => "rounded",
+        }
+    }
+}
+
+/// Characters used to draw a box.
+///
+/// Each one is a single character wide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BorderChars {
+    /// Top-left corner.
+    pub top_left: &'static str,
+    /// Top-right corner.
+    pub top_right: &'static str,
+    /// Bottom-left corner.
+    pub bottom_left: &'static str,
+    /// Bottom-right corner.
+    pub bottom_right: &'static str,
+    /// Top and bottom edges.
+    pub horizontal: &'static str,
+    /// Left and right edges.
+    pub vertical: &'static str,
+    /// Junction between the left edge and a horizontal line.
+    pub left_tee: &'static str,
+    /// Junction between the right edge and a horizontal line.
+    pub right_tee: &'static str,
+    /// Junction between the top edge and a vertical line.
+    pub top_tee: &'static str,
+    /// Junction between the bottom edge and a vertical line.
+    pub bottom_tee: &'static str,
+}
+
+impl BorderChars {
+    /// Single-line box-drawing characters.
+    pub const SINGLE: BorderChars = BorderChars {
+        top_left: "┌",
+        top_right: "┐",
+        bottom_left: "└",
+        bottom_right: "┘",
+        horizontal: "─",
+        vertical: "│",
+        left_tee: "├",
+        right_tee: "┤",
+        top_tee: "┬",
+        bottom_tee: "┴",
+    };
+
+    /// Double-line box-drawing characters.
+    pub const DOUBLE: BorderChars = BorderChars {
+        top_left: "╔",
+        top_right: "╗",
+        bottom_left: "╚",
+        bottom_right: "╝",
+        horizontal: "═",
+        vertical: "║",
+        left_tee: "╠",
+        right_tee: "╣",
+        top_tee: "╦",
+        bottom_tee: "╩",
+    };
+
+    /// Spaces, for invisible borders.
+    pub const BLANK: BorderChars = BorderChars {
+        top_left: " ",
+        top_right: " ",
+        bottom_left: " ",
+        bottom_right: " ",
+        horizontal: " ",
+        vertical: " ",
+        left_tee: " ",
+        right_tee: " ",
+        top_tee: " ",
+        bottom_tee: " ",
+    };
+
+    /// Single-line box-drawing characters, with rounded corners.
+    pub const ROUNDED: BorderChars = BorderChars {
+        top_left: "╭",
+        top_right: "╮",
+        bottom_left: "╰",
+        bottom_right: "╯",
+        horizontal: "─",
+        vertical: "│",
+        left_tee: "├",
+        right_tee: "┤",
+        top_tee: "┬",
+        bottom_tee: "┴",
+    };
+}
+
+impl fmt::Display for BorderStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<S: AsRef<str>> From<S> for BorderStyle {
     fn from(s: S) -> Self {
-        if &*s == "simple" {
-            BorderStyle::Simple
-        } else if &*s == "outset" {
-            BorderStyle::Outset
-        } else {
-            BorderStyle::None
+        BorderStyle::parse(s.as_ref()).unwrap_or(BorderStyle::None)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BorderStyle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BorderStyle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let value = String::deserialize(deserializer)?;
+        BorderStyle::parse(&value).ok_or_else(|| {
+            D::Error::custom(format!("invalid border style `{}`", value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BorderChars, BorderStyle};
+
+    #[test]
+    fn test_roundtrip() {
+        for &style in &[
+            BorderStyle::Simple,
+            BorderStyle::Outset,
+            BorderStyle::None,
+            BorderStyle::Double,
+            BorderStyle::Rounded,
+        ] {
+            assert_eq!(BorderStyle::from(style.as_str()), style);
+            assert_eq!(style.to_string(), style.as_str());
+            assert_eq!(BorderStyle::parse(style.as_str()), Some(style));
+        }
+    }
+
+    #[test]
+    fn test_chars() {
+        let simple = BorderStyle::Simple.chars();
+        assert_eq!(simple.top_left, "┌");
+        assert_eq!(simple.top_right, "┐");
+        assert_eq!(simple.bottom_left, "└");
+        assert_eq!(simple.bottom_right, "┘");
+        assert_eq!(simple.left_tee, "├");
+        assert_eq!(simple.right_tee, "┤");
+
+        assert_eq!(BorderStyle::Outset.chars(), simple);
+    }
+
+    #[test]
+    fn test_corners() {
+        for &(style, corners) in &[
+            (BorderStyle::Simple, ["┌", "┐", "└", "┘"]),
+            (BorderStyle::Outset, ["┌", "┐", "└", "┘"]),
+            (BorderStyle::None, [" ", " ", " ", " "]),
+            (BorderStyle::Double, ["╔", "╗", "╚", "╝"]),
+            (BorderStyle::Rounded, ["╭", "╮", "╰", "╯"]),
+        ] {
+            let chars = style.chars();
+            assert_eq!(
+                [
+                    chars.top_left,
+                    chars.top_right,
+                    chars.bottom_left,
+                    chars.bottom_right
+                ],
+                corners,
+                "{}",
+                style
+            );
         }
+
+        assert_eq!(BorderStyle::None.frame_chars(), BorderChars::SINGLE);
+        assert_eq!(BorderStyle::Double.frame_chars(), BorderChars::DOUBLE);
+    }
+
+    #[test]
+    fn test_double_and_rounded() {
+        assert_eq!(BorderStyle::parse("double"), Some(BorderStyle::Double));
+        assert_eq!(BorderStyle::from("rounded"), BorderStyle::Rounded);
+
+        let double = BorderStyle::Double.chars();
+        assert_eq!(double.top_left, "╔");
+        assert_eq!(double.bottom_right, "╝");
+        assert_eq!(double.horizontal, "═");
+
+        let rounded = BorderStyle::Rounded.chars();
+        assert_eq!(rounded.top_left, "╭");
+        assert_eq!(rounded.top_right, "╮");
+        assert_eq!(rounded.bottom_left, "╰");
+        assert_eq!(rounded.bottom_right, "╯");
+        assert_eq!(rounded.horizontal, BorderChars::SINGLE.horizontal);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(BorderStyle::parse("simpel"), None);
+        assert_eq!(BorderStyle::from("simpel"), BorderStyle::None);
     }
 }
@@ -0,0 +1,172 @@
+use super::{BaseColor, BorderStyle, Color, Error, PaletteColor, Theme};
+use enum_map::EnumMap;
+
+/// Version of the binary format used by `Theme::to_bytes`.
+///
+/// This should change whenever the layout changes.
+const FORMAT_VERSION: u8 = 1;
+
+// Version, shadow, borders and shadow intensity.
+const HEADER_LEN: usize = 7;
+
+// Each color is a tag followed by 3 bytes.
+const COLOR_LEN: usize = 4;
+
+impl Theme {
+    /// Encodes this theme in a compact binary format.
+    ///
+    /// This can be used to cache a theme, to avoid parsing a theme file on
+    /// every startup. Use [`Theme::from_bytes`] to decode it.
+    ///
+    /// The encoding includes the shadow settings, the border style and the
+    /// basic palette colors. Custom palette colors and effects are not
+    /// included.
+    ///
+    /// [`Theme::from_bytes`]: #method.from_bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            HEADER_LEN + COLOR_LEN * self.palette.iter().count(),
+        );
+
+        bytes.push(FORMAT_VERSION);
+        bytes.push(self.shadow as u8);
+        bytes.push(match self.borders {
+            BorderStyle::Simple => 0,
+            BorderStyle::Outset => 1,
+            BorderStyle::None => 2,
+            BorderStyle::Double => 3,
+            BorderStyle::Rounded => 4,
+        });
+        bytes.extend_from_slice(&self.shadow_intensity.to_le_bytes());
+
+        for (_, color) in self.palette.iter() {
+            bytes.extend_from_slice(&encode_color(color));
+        }
+
+        bytes
+    }
+
+    /// Decodes a theme encoded with [`Theme::to_bytes`].
+    ///
+    /// Values not included in the encoding use their default value.
+    ///
+    /// Returns `Error::Decode` if `bytes` is not a valid encoding, or uses a
+    /// different version of the format.
+    ///
+    /// [`Theme::to_bytes`]: #method.to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Theme, Error> {
+        let invalid = |reason: &str| Error::Decode(reason.to_string());
+
+        let roles = EnumMap::from(|role: PaletteColor| role);
+        if bytes.len() != HEADER_LEN + COLOR_LEN * roles.len() {
+            return Err(invalid("unexpected length"));
+        }
+
+        let (header, colors) = bytes.split_at(HEADER_LEN);
+        if header[0] != FORMAT_VERSION {
+            return Err(invalid("unsupported format version"));
+        }
+
+        let shadow = match header[1] {
+            0 => false,
+            1 => true,
+            _ => return Err(invalid("invalid shadow")),
+        };
+        let borders = match header[2] {
+            0 => BorderStyle::Simple,
+            1 => BorderStyle::Outset,
+            2 => BorderStyle::None,
+            3 => BorderStyle::Double,
+            4 => BorderStyle::Rounded,
+            _ => return Err(invalid("invalid border style")),
+        };
+        let shadow_intensity =
+            f32::from_le_bytes([header[3], header[4], header[5], header[6]]);
+
+        let mut theme = Theme {
+            shadow,
+            shadow_intensity,
+            borders,
+            ..Theme::default()
+        };
+        for (&role, color) in roles.values().zip(colors.chunks(COLOR_LEN)) {
+            theme.palette[role] =
+                decode_color(color).ok_or_else(|| invalid("invalid color"))?;
+        }
+
+        Ok(theme)
+    }
+}
+
+fn encode_color(color: Color) -> [u8; COLOR_LEN] {
+    match color {
+        Color::TerminalDefault => [0, 0, 0, 0],
+        Color::Dark(base) => [1, base.to_u8(), 0, 0],
+        Color::Light(base) => [2, base.to_u8(), 0, 0],
+        Color::Rgb(r, g, b) => [3, r, g, b],
+        Color::RgbLowRes(r, g, b) => [4, r, g, b],
+    }
+}
+
+fn decode_color(bytes: &[u8]) -> Option<Color> {
+    let base = || Some(bytes[1]).filter(|&n| n < 8).map(BaseColor::from);
+
+    Some(match bytes[0] {
+        0 => Color::TerminalDefault,
+        1 => Color::Dark(base()?),
+        2 => Color::Light(base()?),
+        3 => Color::Rgb(bytes[1], bytes[2], bytes[3]),
+        4 => Color::low_res(bytes[1], bytes[2], bytes[3])?,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for name in Theme::preset_names() {
+            let mut theme = Theme::named(name).unwrap();
+            theme.shadow = false;
+            theme.shadow_intensity = 0.25;
+            theme.borders = BorderStyle::Outset;
+            theme.palette[PaletteColor::Primary] = Color::TerminalDefault;
+            theme.palette[PaletteColor::Tertiary] = Color::RgbLowRes(1, 2, 3);
+
+            let decoded = Theme::from_bytes(&theme.to_bytes()).unwrap();
+            assert_eq!(decoded.palette, theme.palette, "{}", name);
+            assert_eq!(decoded.shadow, theme.shadow);
+            assert_eq!(decoded.shadow_intensity, theme.shadow_intensity);
+            assert_eq!(decoded.borders, theme.borders);
+        }
+
+        for &borders in &[BorderStyle::Double, BorderStyle::Rounded] {
+            let theme = Theme {
+                borders,
+                ..Theme::default()
+            };
+            let decoded = Theme::from_bytes(&theme.to_bytes()).unwrap();
+            assert_eq!(decoded.borders, borders);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let bytes = Theme::default().to_bytes();
+
+        assert!(Theme::from_bytes(&[]).is_err());
+        assert!(Theme::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // Unknown format version.
+        let mut other = bytes.clone();
+        other[0] = FORMAT_VERSION + 1;
+        assert!(matches!(Theme::from_bytes(&other), Err(Error::Decode(_))));
+
+        // Invalid color tag.
+        let mut other = bytes;
+        other[HEADER_LEN] = 42;
+        assert!(Theme::from_bytes(&other).is_err());
+    }
+}
@@ -1,3 +1,7 @@
+use super::ColorDepth;
+use std::fmt;
+use std::ops::{Add, Mul};
+
 /// One of the 8 base colors.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BaseColor {
@@ -45,6 +49,35 @@ impl BaseColor {
     pub fn light(self) -> Color {
         Color::Light(self)
     }
+
+    /// Returns the ID of this base color, between 0 and 7.
+    ///
+    /// This is the inverse of `BaseColor::from(u8)`.
+    pub fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the 8 base colors, ordered by ID.
+    pub fn all() -> [BaseColor; 8] {
+        use BaseColor::*;
+
+        [Black, Red, Green, Yellow, Blue, Magenta, Cyan, White]
+    }
+}
+
+impl BaseColor {
+    fn name(self) -> &'static str {
+        match self {
+            BaseColor::Black => "black",
+            BaseColor::Red => "red",
+            BaseColor::Green => "green",
+            BaseColor::Yellow => "yellow",
+            BaseColor::Blue => "blue",
+            BaseColor::Magenta => "magenta",
+            BaseColor::Cyan => "cyan",
+            BaseColor::White => "white",
+        }
+    }
 }
 
 impl From<u8> for BaseColor {
@@ -109,13 +142,9 @@ impl Color {
         if n < 8 {
             Color::Dark(BaseColor::from(n))
         } else if n < 16 {
-            Color::Light(BaseColor::from(n))
+            Color::Light(BaseColor::from(n - 8))
         } else if n >= 232 {
-            let n = n - 232;
-            let value = 8 + 10 * n;
-
-            // TODO: returns a Grayscale value?
-            Color::Rgb(value, value, value)
+            Color::gray(n - 232)
         } else {
             let n = n - 16;
             // We support 6*6*6 = 216 colors here
@@ -134,6 +163,16 @@ impl Color {
         }
     }
 
+    /// Creates a gray from the 24-step grayscale ramp of 256-color terminals.
+    ///
+    /// `level` 0 to 23 matches the colors 232 to 255, and gives
+    /// `Color::Rgb(v, v, v)` with `v = 8 + 10 * level`: from `(8, 8, 8)` to
+    /// `(238, 238, 238)`. Levels above 23 are clamped to 23.
+    pub fn gray(level: u8) -> Self {
+        let value = 8 + 10 * level.min(23);
+        Color::Rgb(value, value, value)
+    }
+
     /// Creates a `Color::RgbLowRes` from the given values for red, green and
     /// blue.
     ///
@@ -146,15 +185,415 @@ impl Color {
         }
     }
 
+    /// Creates a `Color::RgbLowRes` from the given values for red, green and
+    /// blue.
+    ///
+    /// Values above 5 are clamped to 5.
+    pub fn low_res_clamped(r: u8, g: u8, b: u8) -> Self {
+        Color::RgbLowRes(r.min(5), g.min(5), b.min(5))
+    }
+
+    /// Returns the red, green and blue components of this color.
+    ///
+    /// Base colors use the standard ANSI values. Since the terminal decides
+    /// how they are actually displayed, this is only an approximation.
+    ///
+    /// Returns `None` for `Color::TerminalDefault`.
+    pub fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        // Colours taken from
+        // https://en.wikipedia.org/wiki/ANSI_escape_code#Colors
+        Some(match self {
+            Color::TerminalDefault => return None,
+            Color::Dark(BaseColor::Black) => (0, 0, 0),
+            Color::Dark(BaseColor::Red) => (170, 0, 0),
+            Color::Dark(BaseColor::Green) => (0, 170, 0),
+            Color::Dark(BaseColor::Yellow) => (170, 85, 0),
+            Color::Dark(BaseColor::Blue) => (0, 0, 170),
+            Color::Dark(BaseColor::Magenta) => (170, 0, 170),
+            Color::Dark(BaseColor::Cyan) => (0, 170, 170),
+            Color::Dark(BaseColor::White) => (170, 170, 170),
+
+            Color::Light(BaseColor::Black) => (85, 85, 85),
+            Color::Light(BaseColor::Red) => (255, 85, 85),
+            Color::Light(BaseColor::Green) => (85, 255, 85),
+            Color::Light(BaseColor::Yellow) => (255, 255, 85),
+            Color::Light(BaseColor::Blue) => (85, 85, 255),
+            Color::Light(BaseColor::Magenta) => (255, 85, 255),
+            Color::Light(BaseColor::Cyan) => (85, 255, 255),
+            Color::Light(BaseColor::White) => (255, 255, 255),
+
+            Color::Rgb(r, g, b) => (r, g, b),
+            // Each step is 255 / 5 = 51
+            Color::RgbLowRes(r, g, b) => (r * 51, g * 51, b * 51),
+        })
+    }
+
+    /// Converts this color to a `Color::Rgb`.
+    ///
+    /// Components are given by [`to_rgb`](#method.to_rgb): low-resolution
+    /// colors are scaled by 51, and base colors use the standard ANSI
+    /// values. `Color::TerminalDefault` is returned unchanged.
+    pub fn to_true_color(&self) -> Color {
+        match self.to_rgb() {
+            Some((r, g, b)) => Color::Rgb(r, g, b),
+            None => *self,
+        }
+    }
+
+    /// Mixes this color with `other`.
+    ///
+    /// `t` is the weight of `other`, between `0.0` (returns `self`) and `1.0`
+    /// (returns `other`). Values outside this range are clamped.
+    ///
+    /// Colors are mixed using their RGB components (see [`to_rgb`]), and the
+    /// result is a `Color::Rgb`. `Color::TerminalDefault` cannot be mixed:
+    /// if either color is the terminal default, the closest of the two
+    /// colors is returned instead.
+    ///
+    /// [`to_rgb`]: #method.to_rgb
+    pub fn mix(self, other: Color, t: f32) -> Color {
+        if t <= 0.0 {
+            return self;
+        } else if t >= 1.0 {
+            return other;
+        }
+
+        match (self.to_rgb(), other.to_rgb()) {
+            (Some((r1, g1, b1)), Some((r2, g2, b2))) => {
+                let mix = |a: u8, b: u8| {
+                    (f32::from(a) * (1.0 - t) + f32::from(b) * t).round() as u8
+                };
+                Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+            }
+            _ if t < 0.5 => self,
+            _ => other,
+        }
+    }
+
+    /// Mixes `a` and `b`, with a different weight for each RGB channel.
+    ///
+    /// `weights` gives the weight of `b` for the red, green and blue
+    /// channels, like the `t` argument of [`mix`]. Each weight is clamped
+    /// between `0.0` (use the channel from `a`) and `1.0` (use the channel
+    /// from `b`).
+    ///
+    /// The result is a `Color::Rgb`. If either color is
+    /// `Color::TerminalDefault`, this falls back to [`mix`] with the average
+    /// weight.
+    ///
+    /// [`mix`]: #method.mix
+    pub fn blend_weighted(
+        a: Color,
+        b: Color,
+        weights: (f32, f32, f32),
+    ) -> Color {
+        let (wr, wg, wb) = weights;
+        let (wr, wg, wb) =
+            (wr.clamp(0.0, 1.0), wg.clamp(0.0, 1.0), wb.clamp(0.0, 1.0));
+
+        match (a.to_rgb(), b.to_rgb()) {
+            (Some((r1, g1, b1)), Some((r2, g2, b2))) => {
+                let mix = |x: u8, y: u8, t: f32| {
+                    (f32::from(x) * (1.0 - t) + f32::from(y) * t).round() as u8
+                };
+                Color::Rgb(mix(r1, r2, wr), mix(g1, g2, wg), mix(b1, b2, wb))
+            }
+            _ => a.mix(b, (wr + wg + wb) / 3.0),
+        }
+    }
+
+    /// Returns `steps` colors going from `from` to `to`.
+    ///
+    /// Colors are evenly spaced, and both ends are included. With a single
+    /// step, only `from` is returned.
+    ///
+    /// See [`mix`](#method.mix).
+    pub fn gradient(from: Color, to: Color, steps: usize) -> Vec<Color> {
+        if steps == 1 {
+            return vec![from];
+        }
+
+        (0..steps)
+            .map(|i| from.mix(to, i as f32 / (steps - 1) as f32))
+            .collect()
+    }
+
+    /// Returns a lighter version of this color.
+    ///
+    /// `amount` is between `0.0` (unchanged) and `1.0` (white).
+    ///
+    /// See [`mix`](#method.mix).
+    pub fn lighten(self, amount: f32) -> Color {
+        self.mix(Color::Rgb(255, 255, 255), amount)
+    }
+
+    /// Returns a darker version of this color.
+    ///
+    /// `amount` is between `0.0` (unchanged) and `1.0` (black).
+    ///
+    /// See [`mix`](#method.mix).
+    pub fn darken(self, amount: f32) -> Color {
+        self.mix(Color::Rgb(0, 0, 0), amount)
+    }
+
+    /// Returns the contrast ratio between this color and `other`.
+    ///
+    /// This follows the [WCAG definition], and ranges from `1.0` (identical
+    /// luminance) to `21.0` (black on white). A ratio of at least `4.5` is
+    /// recommended for text.
+    ///
+    /// [WCAG definition]: https://www.w3.org/TR/WCAG20/#contrast-ratiodef
+    pub fn contrast_ratio(self, other: Color) -> f32 {
+        let a = self.luminance() + 0.05;
+        let b = other.luminance() + 0.05;
+
+        if a > b {
+            a / b
+        } else {
+            b / a
+        }
+    }
+
+    /// Returns the relative luminance of this color, between `0.0` (black)
+    /// and `1.0` (white).
+    ///
+    /// This follows the [WCAG definition], using the RGB components from
+    /// [`to_rgb`]. `Color::TerminalDefault` is treated as black.
+    ///
+    /// [WCAG definition]: https://www.w3.org/TR/WCAG20/#relativeluminancedef
+    /// [`to_rgb`]: #method.to_rgb
+    pub fn luminance(self) -> f32 {
+        let (r, g, b) = self.to_rgb().unwrap_or((0, 0, 0));
+
+        let linear = |c: u8| {
+            let c = f32::from(c) / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+
+    /// Returns a gray color with the same luminance as this color.
+    ///
+    /// The result is a `Color::Rgb` with equal components (see
+    /// [`luminance`]). `Color::TerminalDefault` is returned unchanged.
+    ///
+    /// [`luminance`]: #method.luminance
+    pub fn grayscale(self) -> Color {
+        if self == Color::TerminalDefault {
+            return self;
+        }
+
+        // Convert the linear luminance back to a sRGB component.
+        let luminance = self.luminance();
+        let c = if luminance <= 0.03928 / 12.92 {
+            luminance * 12.92
+        } else {
+            1.055 * luminance.powf(1.0 / 2.4) - 0.055
+        };
+        let c = (c * 255.0).round() as u8;
+
+        Color::Rgb(c, c, c)
+    }
+
+    /// Returns `true` if this color can be displayed exactly with `depth`.
+    ///
+    /// * `Rgb` colors need `TrueColor`.
+    /// * `RgbLowRes` colors need at least `Palette256`.
+    /// * `Light` colors need at least `Basic16`.
+    /// * `Dark` colors and `TerminalDefault` are always representable.
+    ///
+    /// Colors that are not representable will be changed by [`downgrade`].
+    ///
+    /// [`downgrade`]: #method.downgrade
+    pub fn is_representable(self, depth: ColorDepth) -> bool {
+        let required = match self {
+            Color::TerminalDefault | Color::Dark(_) => ColorDepth::Basic8,
+            Color::Light(_) => ColorDepth::Basic16,
+            Color::RgbLowRes(_, _, _) => ColorDepth::Palette256,
+            Color::Rgb(_, _, _) => ColorDepth::TrueColor,
+        };
+
+        depth >= required
+    }
+
+    /// Returns the closest color that can be displayed with the given depth.
+    ///
+    /// * With `TrueColor`, colors are unchanged.
+    /// * With `Palette256`, `Rgb` colors become `RgbLowRes`.
+    /// * With `Basic16`, `Rgb` and `RgbLowRes` colors become the closest
+    ///   dark or light base color.
+    /// * With `Basic8`, colors become the closest dark base color. Light
+    ///   colors use their dark version.
+    ///
+    /// `Color::TerminalDefault` is always unchanged.
+    pub fn downgrade(self, depth: ColorDepth) -> Color {
+        match (self, depth) {
+            (Color::TerminalDefault, _) | (_, ColorDepth::TrueColor) => self,
+            (Color::Rgb(r, g, b), ColorDepth::Palette256) => {
+                let scale = |c: u8| (f32::from(c) / 51.0).round() as u8;
+                Color::RgbLowRes(scale(r), scale(g), scale(b))
+            }
+            (_, ColorDepth::Palette256) => self,
+            (Color::Dark(_), _) => self,
+            (Color::Light(_), ColorDepth::Basic16) => self,
+            (Color::Light(base), ColorDepth::Basic8) => Color::Dark(base),
+            (_, ColorDepth::Basic16) => self.closest(
+                (0..8)
+                    .map(|n| Color::Dark(BaseColor::from(n)))
+                    .chain((0..8).map(|n| Color::Light(BaseColor::from(n)))),
+                Color::distance,
+            ),
+            (_, ColorDepth::Basic8) => self.closest(
+                (0..8).map(|n| Color::Dark(BaseColor::from(n))),
+                Color::distance,
+            ),
+        }
+    }
+
+    /// Returns how this color will be displayed with the given depth.
+    ///
+    /// The color is first [downgraded](#method.downgrade) to `depth`, then
+    /// formatted like with `Display`. This shows what a limited terminal
+    /// will actually use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive_core::theme::{Color, ColorDepth};
+    /// let orange = Color::Rgb(255, 140, 0);
+    /// assert_eq!(orange.display_for(ColorDepth::TrueColor), "#ff8c00");
+    /// assert_eq!(orange.display_for(ColorDepth::Basic8), "yellow");
+    /// ```
+    pub fn display_for(self, depth: ColorDepth) -> String {
+        self.downgrade(depth).to_string()
+    }
+
+    // Returns the candidate closest to `self`, according to `distance`.
+    fn closest<I>(
+        self,
+        candidates: I,
+        distance: fn(Color, Color) -> f32,
+    ) -> Color
+    where
+        I: IntoIterator<Item = Color>,
+    {
+        candidates
+            .into_iter()
+            .map(|color| (color, distance(self, color)))
+            .fold(None, |best: Option<(Color, f32)>, (color, distance)| {
+                match best {
+                    Some((_, best_distance)) if best_distance <= distance => {
+                        best
+                    }
+                    _ => Some((color, distance)),
+                }
+            })
+            .map(|(color, _)| color)
+            .unwrap_or(self)
+    }
+
+    /// Returns the distance between this color and `other`.
+    ///
+    /// This is the euclidean distance between the RGB components of both
+    /// colors (see [`to_rgb`]), where `Color::TerminalDefault` counts as
+    /// black. It ranges from `0.0` (identical colors) to about `441.67`
+    /// (black and white).
+    ///
+    /// [`to_rgb`]: #method.to_rgb
+    pub fn distance(self, other: Color) -> f32 {
+        let (r1, g1, b1) = self.to_rgb().unwrap_or((0, 0, 0));
+        let (r2, g2, b2) = other.to_rgb().unwrap_or((0, 0, 0));
+
+        let d = |a: u8, b: u8| (f32::from(a) - f32::from(b)).powi(2);
+        (d(r1, r2) + d(g1, g2) + d(b1, b2)).sqrt()
+    }
+
+    /// Returns the perceptual distance between this color and `other`.
+    ///
+    /// This uses the "redmean" approximation, which weights each RGB
+    /// component according to how sensitive the human eye is to it. It
+    /// usually gives better matches than [`distance`] when picking the
+    /// closest color, but the result has no fixed unit.
+    ///
+    /// `Color::TerminalDefault` counts as black.
+    ///
+    /// [`distance`]: #method.distance
+    pub fn distance_perceptual(self, other: Color) -> f32 {
+        let (r1, g1, b1) = self.to_rgb().unwrap_or((0, 0, 0));
+        let (r2, g2, b2) = other.to_rgb().unwrap_or((0, 0, 0));
+
+        let r_mean = (f32::from(r1) + f32::from(r2)) / 2.0;
+        let d = |a: u8, b: u8| (f32::from(a) - f32::from(b)).powi(2);
+
+        ((2.0 + r_mean / 256.0) * d(r1, r2)
+            + 4.0 * d(g1, g2)
+            + (2.0 + (255.0 - r_mean) / 256.0) * d(b1, b2))
+        .sqrt()
+    }
+
+    /// Returns the base color that looks the closest to this color.
+    ///
+    /// Colors are compared with their dark version, using
+    /// [`distance_perceptual`]. `Color::TerminalDefault` becomes
+    /// `BaseColor::Black`.
+    ///
+    /// [`distance_perceptual`]: #method.distance_perceptual
+    pub fn to_base_perceptual(&self) -> BaseColor {
+        match self.closest(
+            (0..8).map(|n| Color::Dark(BaseColor::from(n))),
+            Color::distance_perceptual,
+        ) {
+            Color::Dark(base) => base,
+            _ => BaseColor::Black,
+        }
+    }
+
+    /// Returns this color as a `#rrggbb` hexadecimal string.
+    ///
+    /// Colors are first converted using [`to_rgb`](#method.to_rgb).
+    ///
+    /// Returns `None` for `Color::TerminalDefault`.
+    pub fn to_hex_string(self) -> Option<String> {
+        self.to_rgb()
+            .map(|(r, g, b)| format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
     /// Parse a string into a color.
     ///
     /// Examples:
     /// * `"red"` becomes `Color::Dark(BaseColor::Red)`
     /// * `"light green"` becomes `Color::Light(BaseColor::Green)`
+    /// * `"bright green"` also becomes `Color::Light(BaseColor::Green)`, as
+    ///   "bright" is the usual terminal name for these colors.
+    /// * `"color9"` becomes `Color::Light(BaseColor::Red)`
+    /// * `"244"` becomes `Color::from_256colors(244)`
     /// * `"default"` becomes `Color::TerminalDefault`
+    /// * `"transparent"` and `"none"` also become `Color::TerminalDefault`:
+    ///   terminals have no real transparency, but using the terminal default
+    ///   color usually gives the same look.
     /// * `"#123456"` becomes `Color::Rgb(0x12, 0x34, 0x56)`
+    /// * `"#123456ff"` also becomes `Color::Rgb(0x12, 0x34, 0x56)`: the alpha
+    ///   channel is ignored, since terminals don't support transparency.
+    /// * `"lowres(1,2,3)"` becomes `Color::RgbLowRes(1, 2, 3)`
+    /// * `"rgb(255,0,0)"` becomes `Color::Rgb(255, 0, 0)`
+    /// * `"rgb(100%,0%,50%)"` becomes `Color::Rgb(255, 0, 128)`
+    /// * `"rgba(255,0,0,0.5)"` also becomes `Color::Rgb(255, 0, 0)`: like for
+    ///   hex colors, the alpha channel is ignored.
+    /// * `"gray50"` (or `"grey50"`) becomes `Color::Rgb(128, 128, 128)`: the
+    ///   number is a percentage of white, between 0 and 100.
+    ///
+    /// Leading and trailing whitespace is ignored, and repeated spaces in
+    /// names like `"light  red"` count as one.
     pub fn parse(value: &str) -> Option<Self> {
-        Some(match value {
+        let value = value.trim();
+        let name = value.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        Some(match name.as_str() {
             "dark black" | "black" => Color::Dark(BaseColor::Black),
             "dark red" | "red" => Color::Dark(BaseColor::Red),
             "dark green" | "green" => Color::Dark(BaseColor::Green),
@@ -163,16 +602,20 @@ impl Color {
             "dark magenta" | "magenta" => Color::Dark(BaseColor::Magenta),
             "dark cyan" | "cyan" => Color::Dark(BaseColor::Cyan),
             "dark white" | "white" => Color::Dark(BaseColor::White),
-            "light black" => Color::Light(BaseColor::Black),
-            "light red" => Color::Light(BaseColor::Red),
-            "light green" => Color::Light(BaseColor::Green),
-            "light yellow" => Color::Light(BaseColor::Yellow),
-            "light blue" => Color::Light(BaseColor::Blue),
-            "light magenta" => Color::Light(BaseColor::Magenta),
-            "light cyan" => Color::Light(BaseColor::Cyan),
-            "light white" => Color::Light(BaseColor::White),
-            "default" => Color::TerminalDefault,
-            value => {
+            "light black" | "bright black" => Color::Light(BaseColor::Black),
+            "light red" | "bright red" => Color::Light(BaseColor::Red),
+            "light green" | "bright green" => Color::Light(BaseColor::Green),
+            "light yellow" | "bright yellow" => {
+                Color::Light(BaseColor::Yellow)
+            }
+            "light blue" | "bright blue" => Color::Light(BaseColor::Blue),
+            "light magenta" | "bright magenta" => {
+                Color::Light(BaseColor::Magenta)
+            }
+            "light cyan" | "bright cyan" => Color::Light(BaseColor::Cyan),
+            "light white" | "bright white" => Color::Light(BaseColor::White),
+            "default" | "transparent" | "none" => Color::TerminalDefault,
+            _ => {
                 return parse_special(value).or_else(|| {
                     log::warn!("Could not parse color `{}`.", value);
                     None
@@ -180,6 +623,268 @@ impl Color {
             }
         })
     }
+
+    /// Returns the known color name closest to `name`.
+    ///
+    /// This is meant to help with misspelled colors, like `"ylelow"`: names
+    /// accepted by [`parse`] are compared using their edit distance. Returns
+    /// `None` if no name is close enough.
+    ///
+    /// [`parse`]: #method.parse
+    pub fn suggest(name: &str) -> Option<&'static str> {
+        let name = name.to_lowercase();
+        let max_distance = (name.chars().count() / 3).max(1);
+
+        COLOR_NAMES
+            .iter()
+            .map(|&candidate| (candidate, edit_distance(&name, candidate)))
+            .filter(|&(_, distance)| distance <= max_distance)
+            .min_by_key(|&(_, distance)| distance)
+            .map(|(candidate, _)| candidate)
+    }
+
+    /// Returns the first valid color from a list of candidates.
+    ///
+    /// This is how arrays of colors are handled in theme files: invalid
+    /// candidates are skipped, so an unusual color can be listed before a
+    /// more common fallback.
+    ///
+    /// Returns `None` if no candidate is a valid color.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive_core::theme::{BaseColor, Color};
+    /// assert_eq!(
+    ///     Color::first_valid(&["not a color", "blue"]),
+    ///     Some(Color::Dark(BaseColor::Blue))
+    /// );
+    /// ```
+    pub fn first_valid(candidates: &[&str]) -> Option<Color> {
+        candidates.iter().copied().find_map(Color::parse)
+    }
+}
+
+/// Formats this color as in theme files.
+///
+/// The result can be parsed back with [`Color::parse`].
+///
+/// [`Color::parse`]: enum.Color.html#method.parse
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Color::TerminalDefault => write!(f, "default"),
+            Color::Dark(base) => write!(f, "{}", base.name()),
+            Color::Light(base) => write!(f, "light {}", base.name()),
+            Color::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            Color::RgbLowRes(r, g, b) => {
+                write!(f, "lowres({},{},{})", r, g, b)
+            }
+        }
+    }
+}
+
+/// Adds the RGB components of both colors.
+///
+/// Components saturate at 255, and the result is a `Color::Rgb`.
+/// `Color::TerminalDefault` is treated as black.
+impl Add for Color {
+    type Output = Color;
+
+    fn add(self, other: Color) -> Color {
+        let (r1, g1, b1) = self.to_rgb().unwrap_or((0, 0, 0));
+        let (r2, g2, b2) = other.to_rgb().unwrap_or((0, 0, 0));
+
+        Color::Rgb(
+            r1.saturating_add(r2),
+            g1.saturating_add(g2),
+            b1.saturating_add(b2),
+        )
+    }
+}
+
+/// Scales the RGB components of this color.
+///
+/// Components are clamped between 0 and 255, and the result is a
+/// `Color::Rgb`. `Color::TerminalDefault` is treated as black.
+impl Mul<f32> for Color {
+    type Output = Color;
+
+    fn mul(self, factor: f32) -> Color {
+        let (r, g, b) = self.to_rgb().unwrap_or((0, 0, 0));
+        let scale =
+            |c: u8| (f32::from(c) * factor).round().clamp(0.0, 255.0) as u8;
+
+        Color::Rgb(scale(r), scale(g), scale(b))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let value = String::deserialize(deserializer)?;
+        Color::parse(&value).ok_or_else(|| {
+            D::Error::custom(format!("invalid color `{}`", value))
+        })
+    }
+}
+
+impl From<BaseColor> for Color {
+    fn from(base: BaseColor) -> Self {
+        Color::Dark(base)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::Rgb(r, g, b)
+    }
+}
+
+/// Converts to a crossterm color.
+///
+/// Low-resolution colors use the matching index in the 256 colors palette.
+///
+/// Must have the `crossterm` feature enabled.
+#[cfg(feature = "crossterm")]
+impl From<Color> for crossterm::style::Color {
+    fn from(color: Color) -> Self {
+        use crossterm::style::Color as C;
+
+        match color {
+            Color::TerminalDefault => C::Reset,
+            Color::Dark(BaseColor::Black) => C::Black,
+            Color::Dark(BaseColor::Red) => C::DarkRed,
+            Color::Dark(BaseColor::Green) => C::DarkGreen,
+            Color::Dark(BaseColor::Yellow) => C::DarkYellow,
+            Color::Dark(BaseColor::Blue) => C::DarkBlue,
+            Color::Dark(BaseColor::Magenta) => C::DarkMagenta,
+            Color::Dark(BaseColor::Cyan) => C::DarkCyan,
+            Color::Dark(BaseColor::White) => C::Grey,
+            Color::Light(BaseColor::Black) => C::DarkGrey,
+            Color::Light(BaseColor::Red) => C::Red,
+            Color::Light(BaseColor::Green) => C::Green,
+            Color::Light(BaseColor::Yellow) => C::Yellow,
+            Color::Light(BaseColor::Blue) => C::Blue,
+            Color::Light(BaseColor::Magenta) => C::Magenta,
+            Color::Light(BaseColor::Cyan) => C::Cyan,
+            Color::Light(BaseColor::White) => C::White,
+            Color::Rgb(r, g, b) => C::Rgb { r, g, b },
+            Color::RgbLowRes(r, g, b) => {
+                debug_assert!(r <= 5 && g <= 5 && b <= 5);
+                C::AnsiValue(16 + 36 * r + 6 * g + b)
+            }
+        }
+    }
+}
+
+/// Converts from a crossterm color.
+///
+/// Indexed colors are converted with [`Color::from_256colors`].
+///
+/// Must have the `crossterm` feature enabled.
+///
+/// [`Color::from_256colors`]: enum.Color.html#method.from_256colors
+#[cfg(feature = "crossterm")]
+impl From<crossterm::style::Color> for Color {
+    fn from(color: crossterm::style::Color) -> Self {
+        use crossterm::style::Color as C;
+
+        match color {
+            C::Reset => Color::TerminalDefault,
+            C::Black => Color::Dark(BaseColor::Black),
+            C::DarkRed => Color::Dark(BaseColor::Red),
+            C::DarkGreen => Color::Dark(BaseColor::Green),
+            C::DarkYellow => Color::Dark(BaseColor::Yellow),
+            C::DarkBlue => Color::Dark(BaseColor::Blue),
+            C::DarkMagenta => Color::Dark(BaseColor::Magenta),
+            C::DarkCyan => Color::Dark(BaseColor::Cyan),
+            C::Grey => Color::Dark(BaseColor::White),
+            C::DarkGrey => Color::Light(BaseColor::Black),
+            C::Red => Color::Light(BaseColor::Red),
+            C::Green => Color::Light(BaseColor::Green),
+            C::Yellow => Color::Light(BaseColor::Yellow),
+            C::Blue => Color::Light(BaseColor::Blue),
+            C::Magenta => Color::Light(BaseColor::Magenta),
+            C::Cyan => Color::Light(BaseColor::Cyan),
+            C::White => Color::Light(BaseColor::White),
+            C::Rgb { r, g, b } => Color::Rgb(r, g, b),
+            C::AnsiValue(n) => Color::from_256colors(n),
+        }
+    }
+}
+
+/// Color names accepted by `Color::parse`.
+const COLOR_NAMES: [&str; 35] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "dark black",
+    "dark red",
+    "dark green",
+    "dark yellow",
+    "dark blue",
+    "dark magenta",
+    "dark cyan",
+    "dark white",
+    "light black",
+    "light red",
+    "light green",
+    "light yellow",
+    "light blue",
+    "light magenta",
+    "light cyan",
+    "light white",
+    "bright black",
+    "bright red",
+    "bright green",
+    "bright yellow",
+    "bright blue",
+    "bright magenta",
+    "bright cyan",
+    "bright white",
+    "default",
+    "transparent",
+    "none",
+];
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == cb { 0 } else { 1 };
+            let deletion = previous[j + 1] + 1;
+            let insertion = current[j] + 1;
+            current.push(substitution.min(deletion).min(insertion));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
 }
 
 fn parse_special(value: &str) -> Option<Color> {
@@ -187,30 +892,148 @@ fn parse_special(value: &str) -> Option<Color> {
         parse_hex(&value[1..])
     } else if value.starts_with("0x") {
         parse_hex(&value[2..])
-    } else if value.len() == 6 {
-        parse_hex(value)
-    } else if value.len() == 3 {
-        // RGB values between 0 and 5 maybe?
-        // Like 050 for green
-        let rgb: Vec<_> =
-            value.chars().map(|c| c as i16 - '0' as i16).collect();
-
-        assert_eq!(rgb.len(), 3);
-        if rgb.iter().all(|&i| i >= 0 && i < 6) {
-            Some(Color::RgbLowRes(rgb[0] as u8, rgb[1] as u8, rgb[2] as u8))
+    } else if let Some(n) = value.strip_prefix("color") {
+        // Base colors by number, like `color3`.
+        // Only the first 16 colors are accepted here.
+        n.parse::<u8>()
+            .ok()
+            .filter(|&n| n < 16)
+            .map(Color::from_256colors)
+    } else if let Some(args) = value
+        .strip_prefix("lowres(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        parse_low_res(args)
+    } else if let Some(args) = value
+        .strip_prefix("rgb(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        parse_rgb(args, false)
+    } else if let Some(args) = value
+        .strip_prefix("rgba(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        parse_rgb(args, true)
+    } else if let Some(percent) = value
+        .strip_prefix("gray")
+        .or_else(|| value.strip_prefix("grey"))
+    {
+        // Grays by percentage, like `gray50`.
+        let percent = percent.parse::<u8>().ok().filter(|&p| p <= 100)?;
+        let value = ((u16::from(percent) * 255 + 50) / 100) as u8;
+        Some(Color::Rgb(value, value, value))
+    } else if let Some(lowres) = legacy_low_res(value) {
+        // Bare low-resolution colors like `050` used to be accepted, but
+        // were too easy to confuse with other values. Some of them are now
+        // read as 256-color indices.
+        let color = parse_index(value);
+        if color.is_some() {
+            log::warn!(
+                "Bare low-resolution color `{}` is read as a 256-color index, use `{}` for a low-resolution color.",
+                value,
+                lowres
+            );
         } else {
-            None
+            log::warn!(
+                "Bare low-resolution color `{}` is not supported anymore, use `{}` instead.",
+                value,
+                lowres
+            );
         }
+        color
+    } else if let Some(color) = parse_index(value) {
+        Some(color)
+    } else if value.len() == 6 {
+        parse_hex(value)
     } else {
         None
     }
 }
 
+/// Returns the `lowres(...)` form of a bare low-resolution color like `050`.
+///
+/// Returns `None` if `value` does not use this old syntax.
+pub(crate) fn legacy_low_res(value: &str) -> Option<String> {
+    if value.len() != 3 || !value.chars().all(|c| ('0'..='5').contains(&c)) {
+        return None;
+    }
+
+    let args: Vec<String> = value.chars().map(String::from).collect();
+    Some(format!("lowres({})", args.join(",")))
+}
+
+// Parses an index in the 256 colors list, like `244`.
+fn parse_index(value: &str) -> Option<Color> {
+    // Leading zeros are not accepted, to avoid confusion with the old
+    // low-resolution syntax.
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|n| n.to_string() == value)
+        .map(Color::from_256colors)
+}
+
+fn parse_low_res(args: &str) -> Option<Color> {
+    let rgb = args
+        .split(',')
+        .map(|c| c.trim().parse::<u8>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match rgb[..] {
+        [r, g, b] => Color::low_res(r, g, b),
+        _ => None,
+    }
+}
+
+// Parses the arguments of `rgb(...)`, or `rgba(...)` if `alpha` is `true`.
+fn parse_rgb(args: &str, alpha: bool) -> Option<Color> {
+    let args: Vec<&str> = args.split(',').map(str::trim).collect();
+
+    let rgb = match (&args[..], alpha) {
+        ([r, g, b], false) => [r, g, b],
+        ([r, g, b, a], true) => {
+            // Terminals have no transparency: the alpha channel is only
+            // checked, then ignored.
+            a.parse::<f32>().ok().filter(|a| (0.0..=1.0).contains(a))?;
+            [r, g, b]
+        }
+        _ => return None,
+    };
+
+    // Like in CSS, channels are either all percentages or all integers.
+    let percents = rgb.iter().filter(|c| c.ends_with('%')).count();
+    let rgb = match percents {
+        0 => rgb
+            .iter()
+            .map(|c| c.parse::<u8>().ok())
+            .collect::<Option<Vec<_>>>()?,
+        3 => rgb
+            .iter()
+            .map(|c| {
+                c.strip_suffix('%')?
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|p| (0.0..=100.0).contains(p))
+                    .map(|p| (p * 2.55).round() as u8)
+            })
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+
+    Some(Color::Rgb(rgb[0], rgb[1], rgb[2]))
+}
+
 fn parse_hex(value: &str) -> Option<Color> {
+    // Channels are sliced by byte offset below.
+    if !value.is_ascii() {
+        return None;
+    }
+
     // Compute per-color length, and amplitude
+    // Terminals have no transparency: an optional alpha channel is ignored.
     let (l, multiplier) = match value.len() {
-        6 => (2, 1),
-        3 => (1, 17),
+        6 | 8 => (2, 1),
+        3 | 4 => (1, 17),
         _ => return None,
     };
     let r = load_hex(&value[0..l]) * multiplier;
@@ -246,7 +1069,7 @@ fn load_hex(s: &str) -> u16 {
 
 #[cfg(test)]
 mod tests {
-    use super::Color;
+    use super::{legacy_low_res, BaseColor, Color, ColorDepth};
 
     #[test]
     fn test_256_colors() {
@@ -257,6 +1080,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_256_colors_light() {
+        use super::BaseColor::*;
+
+        let expected = [Black, Red, Green, Yellow, Blue, Magenta, Cyan, White];
+        for (i, &base) in expected.iter().enumerate() {
+            let i = i as u8;
+            assert_eq!(Color::from_256colors(i), Color::Dark(base));
+            assert_eq!(Color::from_256colors(i + 8), Color::Light(base));
+            assert_eq!(base.to_u8(), i);
+        }
+    }
+
+    #[test]
+    fn test_gray() {
+        assert_eq!(Color::gray(0), Color::Rgb(8, 8, 8));
+        assert_eq!(Color::gray(23), Color::Rgb(238, 238, 238));
+        assert_eq!(Color::gray(200), Color::gray(23));
+
+        for level in 0..24 {
+            assert_eq!(Color::gray(level), Color::from_256colors(232 + level));
+        }
+    }
+
+    #[test]
+    fn test_256_colors_grayscale() {
+        assert_eq!(Color::from_256colors(232), Color::Rgb(8, 8, 8));
+        assert_eq!(Color::from_256colors(244), Color::Rgb(128, 128, 128));
+        assert_eq!(Color::from_256colors(255), Color::Rgb(238, 238, 238));
+
+        // The ramp is not part of the 6x6x6 cube.
+        for i in 232..=255u8 {
+            match Color::from_256colors(i) {
+                Color::Rgb(r, g, b) => assert!(r == g && g == b),
+                color => panic!("Unexpected color for {}: {:?}", i, color),
+            }
+        }
+    }
+
     #[test]
     fn test_parse() {
         assert_eq!(Color::parse("#fff"), Some(Color::Rgb(255, 255, 255)));
@@ -272,6 +1134,311 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_0x() {
+        assert_eq!(Color::parse("0xff0000"), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("0xf00"), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("0xff00"), Some(Color::Rgb(255, 255, 0)));
+        assert_eq!(Color::parse("0x"), None);
+    }
+
+    #[test]
+    fn test_parse_low_res() {
+        assert_eq!(
+            Color::parse("lowres(1,2,3)"),
+            Some(Color::RgbLowRes(1, 2, 3))
+        );
+        assert_eq!(
+            Color::parse("lowres(5, 0, 5)"),
+            Some(Color::RgbLowRes(5, 0, 5))
+        );
+
+        assert_eq!(Color::parse("lowres(1,2,6)"), None);
+        assert_eq!(Color::parse("lowres(1,2)"), None);
+        assert_eq!(Color::parse("lowres(1,2,3,4)"), None);
+        assert_eq!(Color::parse("lowres(a,b,c)"), None);
+        assert_eq!(Color::parse("lowres(1,2,3"), None);
+    }
+
+    #[test]
+    fn test_parse_bare_low_res() {
+        // Only the explicit `lowres(...)` syntax is accepted.
+        assert_eq!(Color::parse("012"), None);
+        assert_eq!(Color::parse("555"), None);
+        assert_eq!(legacy_low_res("012"), Some("lowres(0,1,2)".to_string()));
+
+        // Some old values are now 256-color indices, but are still noticed.
+        assert_eq!(Color::parse("111"), Some(Color::from_256colors(111)));
+        assert_eq!(legacy_low_res("111"), Some("lowres(1,1,1)".to_string()));
+        assert_eq!(legacy_low_res("116"), None);
+        assert_eq!(legacy_low_res("1111"), None);
+        assert_eq!(
+            Color::parse("lowres(0,1,2)"),
+            Some(Color::RgbLowRes(0, 1, 2))
+        );
+    }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(Color::suggest("ylelow"), Some("yellow"));
+        assert_eq!(Color::suggest("rd"), Some("red"));
+        assert_eq!(Color::suggest("Light Bleu"), Some("light blue"));
+        assert_eq!(Color::suggest("#123456"), None);
+        assert_eq!(Color::suggest("something else"), None);
+    }
+
+    #[test]
+    fn test_first_valid() {
+        assert_eq!(
+            Color::first_valid(&["#zz", "#ff0000", "blue"]),
+            Some(Color::Rgb(255, 0, 0))
+        );
+        assert_eq!(Color::first_valid(&["nope", "nah"]), None);
+        assert_eq!(Color::first_valid(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_256_colors_index() {
+        assert_eq!(Color::parse("0"), Some(Color::Dark(BaseColor::Black)));
+        assert_eq!(Color::parse("9"), Some(Color::Light(BaseColor::Red)));
+        assert_eq!(Color::parse("244"), Some(Color::from_256colors(244)));
+        assert_eq!(Color::parse("255"), Some(Color::from_256colors(255)));
+        assert_eq!(Color::parse("256"), None);
+        assert_eq!(Color::parse("-1"), None);
+        assert_eq!(Color::parse("+5"), None);
+        assert_eq!(Color::parse("007"), None);
+    }
+
+    #[test]
+    fn test_parse_bright() {
+        assert_eq!(
+            Color::parse("bright red"),
+            Some(Color::Light(BaseColor::Red))
+        );
+        assert_eq!(Color::parse("bright red"), Color::parse("light red"));
+        for base in BaseColor::all() {
+            assert_eq!(
+                Color::parse(&format!("bright {}", base.name())),
+                Some(Color::Light(base))
+            );
+        }
+        assert_eq!(Color::suggest("brigth red"), Some("bright red"));
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        assert_eq!(Color::parse(" red "), Some(Color::Dark(BaseColor::Red)));
+        assert_eq!(
+            Color::parse("\tlight  red\n"),
+            Some(Color::Light(BaseColor::Red))
+        );
+        assert_eq!(
+            Color::parse("  #123456 "),
+            Some(Color::Rgb(0x12, 0x34, 0x56))
+        );
+        assert_eq!(Color::parse(" rgb(1, 2, 3) "), Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(Color::parse("   "), None);
+    }
+
+    #[test]
+    fn test_parse_default() {
+        assert_eq!(Color::parse("default"), Some(Color::TerminalDefault));
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!(Color::parse("rgb(255,0,0)"), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("rgb(1, 2, 3)"), Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(Color::parse("rgb(256,0,0)"), None);
+        assert_eq!(Color::parse("rgb(-1,0,0)"), None);
+        assert_eq!(Color::parse("rgb(1,2)"), None);
+        assert_eq!(Color::parse("rgb(1,2,3,0.5)"), None);
+    }
+
+    #[test]
+    fn test_parse_rgb_percent() {
+        assert_eq!(
+            Color::parse("rgb(100%, 0%, 50%)"),
+            Some(Color::Rgb(255, 0, 128))
+        );
+        assert_eq!(
+            Color::parse("rgba(100%,100%,100%,0.5)"),
+            Some(Color::Rgb(255, 255, 255))
+        );
+
+        // Mixed percentages and integers.
+        assert_eq!(Color::parse("rgb(100%, 0, 0)"), None);
+        // Out of range.
+        assert_eq!(Color::parse("rgb(101%, 0%, 0%)"), None);
+        assert_eq!(Color::parse("rgb(-1%, 0%, 0%)"), None);
+        assert_eq!(Color::parse("rgb(1%%, 0%, 0%)"), None);
+    }
+
+    #[test]
+    fn test_parse_rgba() {
+        assert_eq!(
+            Color::parse("rgba(255,0,0,0.5)"),
+            Some(Color::Rgb(255, 0, 0))
+        );
+        assert_eq!(
+            Color::parse("rgba(10, 20, 30, 1)"),
+            Some(Color::Rgb(10, 20, 30))
+        );
+        assert_eq!(Color::parse("rgba(300,0,0,0.5)"), None);
+        assert_eq!(Color::parse("rgba(255,0,0,2)"), None);
+        assert_eq!(Color::parse("rgba(255,0,0)"), None);
+    }
+
+    #[test]
+    fn test_parse_gray() {
+        assert_eq!(Color::parse("gray0"), Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(Color::parse("gray50"), Some(Color::Rgb(128, 128, 128)));
+        assert_eq!(Color::parse("grey100"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(Color::parse("gray150"), None);
+        assert_eq!(Color::parse("gray"), None);
+    }
+
+    #[test]
+    fn test_parse_transparent() {
+        assert_eq!(Color::parse("transparent"), Some(Color::TerminalDefault));
+        assert_eq!(Color::parse("none"), Some(Color::TerminalDefault));
+    }
+
+    #[test]
+    fn test_parse_alpha() {
+        assert_eq!(Color::parse("#ff0000ff"), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("#ff000000"), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("#f008"), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("#ff00000"), None);
+
+        // Multibyte characters are rejected, not sliced.
+        assert_eq!(Color::parse("#aéb"), None);
+        assert_eq!(Color::parse("#ffé000"), None);
+    }
+
+    #[test]
+    fn test_parse_numbered() {
+        assert_eq!(Color::parse("color1"), Some(Color::Dark(BaseColor::Red)));
+        assert_eq!(Color::parse("color9"), Some(Color::Light(BaseColor::Red)));
+        assert_eq!(Color::parse("color16"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let colors = [
+            Color::TerminalDefault,
+            Color::Dark(BaseColor::Red),
+            Color::Light(BaseColor::Cyan),
+            Color::Rgb(255, 0, 0),
+            Color::RgbLowRes(1, 2, 3),
+        ];
+
+        for &color in &colors {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Color::Rgb(255, 0, 0)).unwrap(),
+            r##""#ff0000""##
+        );
+        assert!(serde_json::from_str::<Color>(r#""rd""#).is_err());
+    }
+
+    #[test]
+    fn test_to_hex_string() {
+        assert_eq!(
+            Color::Rgb(255, 0, 0).to_hex_string(),
+            Some("#ff0000".to_string())
+        );
+        assert_eq!(
+            Color::RgbLowRes(5, 1, 0).to_hex_string(),
+            Some("#ff3300".to_string())
+        );
+        assert_eq!(
+            Color::Light(BaseColor::White).to_hex_string(),
+            Some("#ffffff".to_string())
+        );
+        assert_eq!(Color::TerminalDefault.to_hex_string(), None);
+    }
+
+    #[test]
+    fn test_mix() {
+        let red = Color::Rgb(255, 0, 0);
+        let blue = Color::Dark(BaseColor::Blue);
+
+        assert_eq!(red.mix(blue, 0.0), red);
+        assert_eq!(red.mix(blue, 1.0), blue);
+        assert_eq!(red.mix(blue, 0.5), Color::Rgb(128, 0, 85));
+        assert_eq!(red.mix(blue, 2.0), blue);
+
+        assert_eq!(red.mix(Color::TerminalDefault, 0.2), red);
+        assert_eq!(
+            red.mix(Color::TerminalDefault, 0.8),
+            Color::TerminalDefault
+        );
+    }
+
+    #[test]
+    fn test_blend_weighted() {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+
+        assert_eq!(
+            Color::blend_weighted(black, white, (1.0, 0.5, 0.0)),
+            Color::Rgb(255, 128, 0)
+        );
+        assert_eq!(
+            Color::blend_weighted(black, white, (2.0, -1.0, 0.2)),
+            Color::Rgb(255, 0, 51)
+        );
+        assert_eq!(
+            Color::blend_weighted(black, white, (0.5, 0.5, 0.5)),
+            black.mix(white, 0.5)
+        );
+        assert_eq!(
+            Color::blend_weighted(
+                black,
+                Color::TerminalDefault,
+                (1.0, 1.0, 0.7)
+            ),
+            Color::TerminalDefault
+        );
+    }
+
+    #[test]
+    fn test_ops() {
+        let gray = Color::Rgb(100, 150, 200);
+
+        assert_eq!(gray + gray, Color::Rgb(200, 255, 255));
+        assert_eq!(
+            Color::Dark(BaseColor::Red) + Color::Dark(BaseColor::Blue),
+            Color::Rgb(170, 0, 170)
+        );
+        assert_eq!(gray + Color::TerminalDefault, gray);
+
+        assert_eq!(gray * 0.5, Color::Rgb(50, 75, 100));
+        assert_eq!(gray * 2.0, Color::Rgb(200, 255, 255));
+        assert_eq!(gray * -1.0, Color::Rgb(0, 0, 0));
+        assert_eq!(Color::TerminalDefault * 2.0, Color::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_gradient() {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Light(BaseColor::White);
+
+        let gradient = Color::gradient(black, white, 5);
+        assert_eq!(gradient.len(), 5);
+        assert_eq!(gradient[0], black);
+        assert_eq!(gradient[2], Color::Rgb(128, 128, 128));
+        assert_eq!(gradient[4], white);
+
+        assert_eq!(Color::gradient(black, white, 1), vec![black]);
+        assert!(Color::gradient(black, white, 0).is_empty());
+    }
+
     #[test]
     fn test_low_res() {
         // Make sure Color::low_res always works with valid ranges.
@@ -304,4 +1471,247 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_to_true_color() {
+        assert_eq!(
+            Color::RgbLowRes(1, 2, 5).to_true_color(),
+            Color::Rgb(51, 102, 255)
+        );
+        assert_eq!(Color::Rgb(1, 2, 3).to_true_color(), Color::Rgb(1, 2, 3));
+        assert_eq!(
+            Color::Light(BaseColor::Red).to_true_color(),
+            Color::Rgb(255, 85, 85)
+        );
+        assert_eq!(
+            Color::TerminalDefault.to_true_color(),
+            Color::TerminalDefault
+        );
+    }
+
+    #[test]
+    fn test_low_res_clamped() {
+        assert_eq!(Color::low_res_clamped(1, 2, 3), Color::RgbLowRes(1, 2, 3));
+        assert_eq!(
+            Color::low_res_clamped(6, 0, 255),
+            Color::RgbLowRes(5, 0, 5)
+        );
+    }
+
+    #[test]
+    fn test_base_color_to_u8() {
+        for n in 0..8 {
+            assert_eq!(BaseColor::from(n).to_u8(), n);
+        }
+    }
+
+    #[test]
+    fn test_base_color_all() {
+        for (i, &base) in BaseColor::all().iter().enumerate() {
+            assert_eq!(base.to_u8() as usize, i);
+        }
+    }
+
+    #[test]
+    fn test_luminance() {
+        assert_eq!(Color::Rgb(0, 0, 0).luminance(), 0.0);
+        assert_eq!(Color::Rgb(255, 255, 255).luminance(), 1.0);
+        assert_eq!(Color::TerminalDefault.luminance(), 0.0);
+        assert!(
+            Color::Rgb(0, 255, 0).luminance()
+                > Color::Rgb(255, 0, 0).luminance()
+        );
+    }
+
+    #[test]
+    fn test_grayscale() {
+        let gray = Color::Rgb(255, 0, 0).grayscale();
+        match gray {
+            Color::Rgb(r, g, b) => {
+                assert!(r == g && g == b);
+                assert!((gray.luminance() - 0.2126).abs() < 0.01);
+            }
+            other => panic!("Expected a RGB color, got {:?}", other),
+        }
+
+        assert_eq!(
+            Color::Rgb(100, 100, 100).grayscale(),
+            Color::Rgb(100, 100, 100)
+        );
+        assert_eq!(
+            Color::Light(BaseColor::White).grayscale(),
+            Color::Rgb(255, 255, 255)
+        );
+        assert_eq!(Color::TerminalDefault.grayscale(), Color::TerminalDefault);
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+
+        assert!((black.contrast_ratio(white) - 21.0).abs() < 1e-3);
+        assert!((white.contrast_ratio(black) - 21.0).abs() < 1e-3);
+        assert!((white.contrast_ratio(white) - 1.0).abs() < 1e-3);
+
+        assert_eq!(Color::Rgb(255, 0, 0).lighten(1.0), white);
+        assert_eq!(Color::Rgb(255, 0, 0).darken(0.5), Color::Rgb(128, 0, 0));
+    }
+
+    #[test]
+    fn test_is_representable() {
+        use super::ColorDepth::*;
+
+        let depths = [Basic8, Basic16, Palette256, TrueColor];
+        let cases = [
+            (Color::TerminalDefault, [true, true, true, true]),
+            (Color::Dark(BaseColor::Red), [true, true, true, true]),
+            (Color::Light(BaseColor::Red), [false, true, true, true]),
+            (Color::RgbLowRes(1, 2, 3), [false, false, true, true]),
+            (Color::Rgb(1, 2, 3), [false, false, false, true]),
+        ];
+
+        for &(color, expected) in &cases {
+            for (&depth, &expected) in depths.iter().zip(&expected) {
+                assert_eq!(
+                    color.is_representable(depth),
+                    expected,
+                    "{:?} with {:?}",
+                    color,
+                    depth
+                );
+                // Representable colors are not changed when downgrading.
+                assert_eq!(color.downgrade(depth) == color, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_for() {
+        use super::ColorDepth::*;
+
+        let red = Color::Rgb(250, 10, 10);
+        assert_eq!(red.display_for(Basic8), "red");
+        assert_eq!(red.display_for(Palette256), "lowres(5,0,0)");
+        assert_eq!(red.display_for(TrueColor), "#fa0a0a");
+        assert_eq!(Color::Light(BaseColor::Cyan).display_for(Basic8), "cyan");
+        assert_eq!(Color::TerminalDefault.display_for(Basic8), "default");
+    }
+
+    #[test]
+    fn test_downgrade() {
+        use super::ColorDepth::*;
+
+        let orange = Color::Rgb(255, 140, 0);
+        assert_eq!(orange.downgrade(TrueColor), orange);
+        assert_eq!(orange.downgrade(Palette256), Color::RgbLowRes(5, 3, 0));
+        assert_eq!(orange.downgrade(Basic16), Color::Dark(BaseColor::Yellow));
+        assert_eq!(orange.downgrade(Basic8), Color::Dark(BaseColor::Yellow));
+
+        let light_blue = Color::Light(BaseColor::Blue);
+        assert_eq!(light_blue.downgrade(Palette256), light_blue);
+        assert_eq!(light_blue.downgrade(Basic16), light_blue);
+        assert_eq!(light_blue.downgrade(Basic8), Color::Dark(BaseColor::Blue));
+
+        assert_eq!(
+            Color::RgbLowRes(0, 0, 0).downgrade(Basic16),
+            Color::Dark(BaseColor::Black)
+        );
+        assert_eq!(
+            Color::TerminalDefault.downgrade(Basic8),
+            Color::TerminalDefault
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+
+        assert_eq!(black.distance(black), 0.0);
+        assert_eq!(
+            Color::Dark(BaseColor::Red).distance(Color::Rgb(170, 0, 0)),
+            0.0
+        );
+
+        let max = black.distance(white);
+        assert!((max - 441.673).abs() < 1e-3);
+        assert_eq!(Color::distance(white, black), max);
+        assert!(Color::Rgb(12, 200, 99).distance(white) < max);
+    }
+
+    #[test]
+    fn test_distance_perceptual() {
+        let black = Color::Rgb(0, 0, 0);
+        assert_eq!(black.distance_perceptual(black), 0.0);
+        assert_eq!(
+            Color::Rgb(200, 10, 10).to_base_perceptual(),
+            BaseColor::Red
+        );
+
+        // This dark olive is closer to black in plain RGB, but looks more
+        // like yellow.
+        let olive = Color::Rgb(60, 75, 0);
+        assert_eq!(
+            olive.downgrade(ColorDepth::Basic8),
+            Color::Dark(BaseColor::Black)
+        );
+        assert_eq!(olive.to_base_perceptual(), BaseColor::Yellow);
+    }
+
+    #[test]
+    fn test_display() {
+        let colors = [
+            Color::TerminalDefault,
+            Color::Dark(BaseColor::Red),
+            Color::Light(BaseColor::Cyan),
+            Color::Rgb(0x12, 0xab, 0xff),
+            Color::RgbLowRes(1, 2, 3),
+        ];
+
+        for &color in &colors {
+            assert_eq!(Color::parse(&color.to_string()), Some(color));
+        }
+
+        assert_eq!(Color::Light(BaseColor::Blue).to_string(), "light blue");
+        assert_eq!(Color::Rgb(255, 0, 0).to_string(), "#ff0000");
+        assert_eq!(Color::RgbLowRes(1, 2, 3).to_string(), "lowres(1,2,3)");
+    }
+
+    #[test]
+    fn test_from_base_color() {
+        assert_eq!(Color::from(BaseColor::Red), Color::Dark(BaseColor::Red));
+        assert_eq!(Color::from(BaseColor::Red), BaseColor::Red.dark());
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn test_crossterm() {
+        use crossterm::style::Color as C;
+
+        assert_eq!(C::from(Color::TerminalDefault), C::Reset);
+        assert_eq!(C::from(Color::Dark(BaseColor::White)), C::Grey);
+        assert_eq!(C::from(Color::Light(BaseColor::Red)), C::Red);
+        assert_eq!(C::from(Color::Rgb(1, 2, 3)), C::Rgb { r: 1, g: 2, b: 3 });
+        assert_eq!(C::from(Color::RgbLowRes(1, 2, 3)), C::AnsiValue(67));
+
+        assert_eq!(Color::from(C::Reset), Color::TerminalDefault);
+        assert_eq!(Color::from(C::DarkGrey), Color::Light(BaseColor::Black));
+        assert_eq!(Color::from(C::AnsiValue(67)), Color::RgbLowRes(1, 2, 3));
+
+        // Every base color survives a round-trip.
+        for &base in BaseColor::all().iter() {
+            for &color in &[Color::Dark(base), Color::Light(base)] {
+                assert_eq!(Color::from(C::from(color)), color);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_tuple() {
+        assert_eq!(Color::from((211, 215, 207)), Color::Rgb(211, 215, 207));
+
+        let color: Color = (1, 2, 3).into();
+        assert_eq!(color.to_rgb(), Some((1, 2, 3)));
+    }
 }
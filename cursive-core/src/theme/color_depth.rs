@@ -0,0 +1,68 @@
+use std::env;
+
+/// Number of colors a terminal can display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// Only the 8 dark base colors.
+    Basic8,
+    /// The 8 base colors, in their dark and light versions.
+    Basic16,
+    /// The 256 colors palette.
+    ///
+    /// This includes the 16 base colors and the 216 low-resolution colors.
+    Palette256,
+    /// True-color, 24-bit.
+    TrueColor,
+}
+
+/// Guesses the color depth of the current terminal.
+///
+/// This looks at the `COLORTERM` and `TERM` environment variables:
+///
+/// * `COLORTERM=truecolor` or `COLORTERM=24bit` means `TrueColor`.
+/// * A `TERM` ending with `256color` (like `xterm-256color`) means
+///   `Palette256`.
+/// * Anything else is assumed to be `Basic16`.
+pub fn detect_color_depth() -> ColorDepth {
+    let colorterm = env::var("COLORTERM").ok();
+    let term = env::var("TERM").ok();
+
+    color_depth_from(colorterm.as_deref(), term.as_deref())
+}
+
+fn color_depth_from(
+    colorterm: Option<&str>,
+    term: Option<&str>,
+) -> ColorDepth {
+    match (colorterm, term) {
+        (Some("truecolor"), _) | (Some("24bit"), _) => ColorDepth::TrueColor,
+        (_, Some(term)) if term.ends_with("256color") => {
+            ColorDepth::Palette256
+        }
+        _ => ColorDepth::Basic16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_depth_from() {
+        use self::ColorDepth::*;
+
+        let cases = [
+            (Some("truecolor"), Some("xterm-256color"), TrueColor),
+            (Some("24bit"), None, TrueColor),
+            (None, Some("xterm-256color"), Palette256),
+            (Some("yes"), Some("screen-256color"), Palette256),
+            (None, Some("xterm"), Basic16),
+            (None, Some("linux"), Basic16),
+            (None, None, Basic16),
+        ];
+
+        for &(colorterm, term, depth) in &cases {
+            assert_eq!(color_depth_from(colorterm, term), depth);
+        }
+    }
+}
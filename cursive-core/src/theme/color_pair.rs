@@ -1,4 +1,4 @@
-use super::Color;
+use super::{Color, Effect};
 
 /// Combines a front and back color.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -11,6 +11,12 @@ pub struct ColorPair {
 }
 
 impl ColorPair {
+    /// A color pair using the terminal default colors for front and back.
+    pub const DEFAULT: ColorPair = ColorPair {
+        front: Color::TerminalDefault,
+        back: Color::TerminalDefault,
+    };
+
     /// Return an inverted color pair.
     ///
     /// With swapped front and back color.
@@ -21,6 +27,34 @@ impl ColorPair {
         }
     }
 
+    /// Returns the colors actually used when `effect` is applied.
+    ///
+    /// Only `Effect::Reverse` changes the colors, by inverting them.
+    pub fn apply_effect(self, effect: Effect) -> Self {
+        match effect {
+            Effect::Reverse => self.invert(),
+            Effect::Simple
+            | Effect::Bold
+            | Effect::Italic
+            | Effect::Strikethrough
+            | Effect::Underline => self,
+        }
+    }
+
+    /// Simulates drawing this pair with the given opacity over `background`.
+    ///
+    /// Both colors are mixed with `background`: an `alpha` of `1.0` keeps
+    /// them unchanged, while `0.0` replaces them with `background`.
+    /// `alpha` is clamped between `0.0` and `1.0`.
+    pub fn blend_over(self, background: Color, alpha: f32) -> Self {
+        let t = 1.0 - alpha.clamp(0.0, 1.0);
+
+        ColorPair {
+            front: self.front.mix(background, t),
+            back: self.back.mix(background, t),
+        }
+    }
+
     /// Creates a new color pair from color IDs.
     pub fn from_256colors(front: u8, back: u8) -> Self {
         Self {
@@ -28,4 +62,87 @@ impl ColorPair {
             back: Color::from_256colors(back),
         }
     }
+
+    /// Creates a new color pair from two color strings, like `"#ff0000"`.
+    ///
+    /// Colors are parsed with [`Color::parse`]. Returns `None` if either
+    /// color is invalid.
+    ///
+    /// [`Color::parse`]: enum.Color.html#method.parse
+    pub fn from_hex_pair(front: &str, back: &str) -> Option<Self> {
+        Some(Self {
+            front: Color::parse(front)?,
+            back: Color::parse(back)?,
+        })
+    }
+}
+
+impl Default for ColorPair {
+    /// Returns `ColorPair::DEFAULT`, using the terminal default colors.
+    fn default() -> Self {
+        ColorPair::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::BaseColor;
+
+    #[test]
+    fn test_default() {
+        let pair = ColorPair::default();
+        assert_eq!(pair, ColorPair::DEFAULT);
+        assert_eq!(pair.front, Color::TerminalDefault);
+        assert_eq!(pair.back, Color::TerminalDefault);
+    }
+
+    #[test]
+    fn test_apply_effect() {
+        let pair = ColorPair {
+            front: Color::Dark(BaseColor::Red),
+            back: Color::Light(BaseColor::White),
+        };
+
+        assert_eq!(pair.apply_effect(Effect::Reverse), pair.invert());
+        for &effect in &[
+            Effect::Simple,
+            Effect::Bold,
+            Effect::Italic,
+            Effect::Strikethrough,
+            Effect::Underline,
+        ] {
+            assert_eq!(pair.apply_effect(effect), pair);
+        }
+    }
+
+    #[test]
+    fn test_from_hex_pair() {
+        assert_eq!(
+            ColorPair::from_hex_pair("#ff0000", "#000"),
+            Some(ColorPair {
+                front: Color::Rgb(255, 0, 0),
+                back: Color::Rgb(0, 0, 0),
+            })
+        );
+
+        assert_eq!(ColorPair::from_hex_pair("#ff0000", "nope"), None);
+        assert_eq!(ColorPair::from_hex_pair("nope", "#000"), None);
+    }
+
+    #[test]
+    fn test_blend_over() {
+        let pair = ColorPair {
+            front: Color::Rgb(255, 0, 0),
+            back: Color::Rgb(0, 0, 0),
+        };
+        let white = Color::Rgb(255, 255, 255);
+
+        let blended = pair.blend_over(white, 0.5);
+        assert_eq!(blended.front, Color::Rgb(255, 128, 128));
+        assert_eq!(blended.back, Color::Rgb(128, 128, 128));
+
+        assert_eq!(pair.blend_over(white, 1.0), pair);
+        assert_eq!(pair.blend_over(white, -1.0).front, white);
+    }
 }
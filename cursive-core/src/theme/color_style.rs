@@ -1,4 +1,77 @@
-use super::{BaseColor, Color, ColorPair, Palette, PaletteColor};
+use super::{BaseColor, Color, ColorPair, Palette, PaletteColor, Theme};
+
+// Built-in styles, in the order returned by `ColorStyle::all`.
+const ALL_STYLES: [ColorStyle; 16] = {
+    use self::ColorType::{Color as C, Palette as P};
+    use self::PaletteColor::*;
+
+    [
+        ColorStyle {
+            front: C(Color::TerminalDefault),
+            back: C(Color::TerminalDefault),
+        },
+        ColorStyle {
+            front: P(Background),
+            back: P(Background),
+        },
+        ColorStyle {
+            front: P(Shadow),
+            back: P(Shadow),
+        },
+        ColorStyle {
+            front: P(Primary),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(Secondary),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(Tertiary),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(TitlePrimary),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(TitleSecondary),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(TitleFocused),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(HighlightText),
+            back: P(Highlight),
+        },
+        ColorStyle {
+            front: P(HighlightText),
+            back: P(HighlightInactive),
+        },
+        ColorStyle {
+            front: P(Primary),
+            back: P(RowEven),
+        },
+        ColorStyle {
+            front: P(Primary),
+            back: P(RowOdd),
+        },
+        ColorStyle {
+            front: P(Error),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(Warning),
+            back: P(View),
+        },
+        ColorStyle {
+            front: P(Success),
+            back: P(View),
+        },
+    ]
+};
 
 /// Possible color style for a cell.
 ///
@@ -66,6 +139,11 @@ impl ColorStyle {
         Self::new(PaletteColor::TitleSecondary, PaletteColor::View)
     }
 
+    /// Title of a focused view, with default background.
+    pub fn title_focused() -> Self {
+        Self::new(PaletteColor::TitleFocused, PaletteColor::View)
+    }
+
     /// Alternate text with highlight background.
     pub fn highlight() -> Self {
         Self::new(PaletteColor::HighlightText, PaletteColor::Highlight)
@@ -76,6 +154,90 @@ impl ColorStyle {
         Self::new(PaletteColor::HighlightText, PaletteColor::HighlightInactive)
     }
 
+    /// Primary text color, with the background of even rows.
+    pub fn row_even() -> Self {
+        Self::new(PaletteColor::Primary, PaletteColor::RowEven)
+    }
+
+    /// Primary text color, with the background of odd rows.
+    pub fn row_odd() -> Self {
+        Self::new(PaletteColor::Primary, PaletteColor::RowOdd)
+    }
+
+    /// Error text color, with default background.
+    pub fn error() -> Self {
+        Self::new(PaletteColor::Error, PaletteColor::View)
+    }
+
+    /// Warning text color, with default background.
+    pub fn warning() -> Self {
+        Self::new(PaletteColor::Warning, PaletteColor::View)
+    }
+
+    /// Success text color, with default background.
+    pub fn success() -> Self {
+        Self::new(PaletteColor::Success, PaletteColor::View)
+    }
+
+    /// Returns every built-in style.
+    ///
+    /// These are, in order: `terminal_default`, `background`, `shadow`,
+    /// `primary`, `secondary`, `tertiary`, `title_primary`,
+    /// `title_secondary`, `title_focused`, `highlight`,
+    /// `highlight_inactive`, `row_even`, `row_odd`, `error`, `warning` and
+    /// `success`.
+    pub fn all() -> &'static [ColorStyle] {
+        &ALL_STYLES
+    }
+
+    /// Returns an inverted style, with swapped front and back colors.
+    ///
+    /// Resolving the result is the same as inverting the resolved pair.
+    pub fn invert(self) -> Self {
+        ColorStyle {
+            front: self.back,
+            back: self.front,
+        }
+    }
+
+    /// Returns the color pair for this style, without terminal defaults.
+    ///
+    /// This resolves the style with the palette from `theme`, then replaces
+    /// each `Color::TerminalDefault` with the matching color from
+    /// `fallback`. This helps backends that cannot use the terminal default
+    /// colors.
+    pub fn resolve_with_default(
+        &self,
+        theme: &Theme,
+        fallback: ColorPair,
+    ) -> ColorPair {
+        let pair = self.resolve(&theme.palette);
+        let or_fallback = |color, fallback| match color {
+            Color::TerminalDefault => fallback,
+            color => color,
+        };
+
+        ColorPair {
+            front: or_fallback(pair.front, fallback.front),
+            back: or_fallback(pair.back, fallback.back),
+        }
+    }
+
+    /// Returns the color pair for this style, inheriting unset roles.
+    ///
+    /// Palette roles set to `Color::TerminalDefault` use the color of the
+    /// roles they inherit from instead (see
+    /// [`PaletteColor::fallback_chain`]). This lets a minimal palette only
+    /// set `Primary`, and have `Secondary` or `Tertiary` use the same color.
+    ///
+    /// [`PaletteColor::fallback_chain`]: enum.PaletteColor.html#method.fallback_chain
+    pub fn resolve_with_fallbacks(&self, palette: &Palette) -> ColorPair {
+        ColorPair {
+            front: self.front.resolve_with_fallbacks(palette),
+            back: self.back.resolve_with_fallbacks(palette),
+        }
+    }
+
     /// Return the color pair that this style represents.
     pub fn resolve(&self, palette: &Palette) -> ColorPair {
         ColorPair {
@@ -119,6 +281,18 @@ where
     }
 }
 
+/// Resolves each style with the palette from `theme`.
+///
+/// This is the same as calling [`ColorStyle::resolve`] on each style.
+///
+/// [`ColorStyle::resolve`]: struct.ColorStyle.html#method.resolve
+pub fn resolve_styles(styles: &[ColorStyle], theme: &Theme) -> Vec<ColorPair> {
+    styles
+        .iter()
+        .map(|style| style.resolve(&theme.palette))
+        .collect()
+}
+
 /// Either a color from the palette, or a direct color.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ColorType {
@@ -137,6 +311,18 @@ impl ColorType {
             ColorType::Palette(color) => color.resolve(palette),
         }
     }
+
+    /// Resolves `self`, inheriting from other roles for unset colors.
+    ///
+    /// See [`PaletteColor::resolve_with_fallbacks`].
+    ///
+    /// [`PaletteColor::resolve_with_fallbacks`]: enum.PaletteColor.html#method.resolve_with_fallbacks
+    pub fn resolve_with_fallbacks(self, palette: &Palette) -> Color {
+        match self {
+            ColorType::Color(color) => color,
+            ColorType::Palette(color) => color.resolve_with_fallbacks(palette),
+        }
+    }
 }
 
 impl From<Color> for ColorType {
@@ -150,3 +336,131 @@ impl From<PaletteColor> for ColorType {
         ColorType::Palette(color)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        assert_eq!(
+            ColorStyle::all(),
+            &[
+                ColorStyle::terminal_default(),
+                ColorStyle::background(),
+                ColorStyle::shadow(),
+                ColorStyle::primary(),
+                ColorStyle::secondary(),
+                ColorStyle::tertiary(),
+                ColorStyle::title_primary(),
+                ColorStyle::title_secondary(),
+                ColorStyle::title_focused(),
+                ColorStyle::highlight(),
+                ColorStyle::highlight_inactive(),
+                ColorStyle::row_even(),
+                ColorStyle::row_odd(),
+                ColorStyle::error(),
+                ColorStyle::warning(),
+                ColorStyle::success(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_default() {
+        let theme = Theme::default();
+        let fallback = ColorPair {
+            front: Color::Rgb(255, 255, 255),
+            back: Color::Rgb(0, 0, 0),
+        };
+
+        assert_eq!(
+            ColorStyle::terminal_default()
+                .resolve_with_default(&theme, fallback),
+            fallback
+        );
+
+        let style =
+            ColorStyle::new(Color::TerminalDefault, PaletteColor::View);
+        assert_eq!(
+            style.resolve_with_default(&theme, fallback),
+            ColorPair {
+                front: Color::Rgb(255, 255, 255),
+                back: theme.palette[PaletteColor::View],
+            }
+        );
+
+        assert_eq!(
+            ColorStyle::primary().resolve_with_default(&theme, fallback),
+            ColorStyle::primary().resolve(&theme.palette)
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_fallbacks() {
+        // Only `Primary` and `View` are set.
+        let mut palette =
+            Palette::default().map_colors(|_| Color::TerminalDefault);
+        palette[PaletteColor::Primary] = Color::Rgb(10, 20, 30);
+        palette[PaletteColor::View] = Color::Rgb(250, 250, 250);
+
+        let expected = ColorPair {
+            front: Color::Rgb(10, 20, 30),
+            back: Color::Rgb(250, 250, 250),
+        };
+        for style in &[
+            ColorStyle::primary(),
+            ColorStyle::secondary(),
+            ColorStyle::tertiary(),
+            ColorStyle::title_secondary(),
+        ] {
+            assert_eq!(style.resolve_with_fallbacks(&palette), expected);
+        }
+
+        // Set roles are used first.
+        palette[PaletteColor::Secondary] = Color::Rgb(1, 2, 3);
+        assert_eq!(
+            ColorStyle::tertiary()
+                .resolve_with_fallbacks(&palette)
+                .front,
+            Color::Rgb(1, 2, 3)
+        );
+
+        // Plain `resolve` keeps the terminal default.
+        assert_eq!(
+            ColorStyle::title_primary().resolve(&palette).front,
+            Color::TerminalDefault
+        );
+        assert_eq!(
+            ColorStyle::background().resolve_with_fallbacks(&palette),
+            ColorStyle::background().resolve(&palette)
+        );
+    }
+
+    #[test]
+    fn test_resolve_styles() {
+        let theme = Theme::default();
+        let pairs = resolve_styles(ColorStyle::all(), &theme);
+
+        assert_eq!(pairs.len(), ColorStyle::all().len());
+        assert_eq!(pairs[3], ColorStyle::primary().resolve(&theme.palette));
+        assert!(resolve_styles(&[], &theme).is_empty());
+    }
+
+    #[test]
+    fn test_invert() {
+        let palette = Palette::default();
+
+        for &style in &[
+            ColorStyle::primary(),
+            ColorStyle::highlight(),
+            ColorStyle::new(Color::Rgb(1, 2, 3), PaletteColor::View),
+        ] {
+            assert_eq!(
+                style.invert().resolve(&palette),
+                style.resolve(&palette).invert()
+            );
+            assert_eq!(style.invert().invert(), style);
+        }
+    }
+}
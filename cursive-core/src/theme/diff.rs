@@ -0,0 +1,209 @@
+use super::{BorderStyle, Color, Effect, PaletteColor, PaletteNode, Theme};
+use enumset::EnumSet;
+
+/// Difference between two themes, as reported by [`Theme::diff`].
+///
+/// [`Theme::diff`]: struct.Theme.html#method.diff
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThemeChange {
+    /// The `shadow` flag changed.
+    Shadow {
+        /// Previous value.
+        old: bool,
+        /// New value.
+        new: bool,
+    },
+
+    /// The shadow intensity changed.
+    ShadowIntensity {
+        /// Previous value.
+        old: f32,
+        /// New value.
+        new: f32,
+    },
+
+    /// The border style changed.
+    Borders {
+        /// Previous value.
+        old: BorderStyle,
+        /// New value.
+        new: BorderStyle,
+    },
+
+    /// The color for a palette role changed.
+    Color {
+        /// Role that changed.
+        role: PaletteColor,
+        /// Previous value.
+        old: Color,
+        /// New value.
+        new: Color,
+    },
+
+    /// A custom palette entry changed.
+    ///
+    /// This covers both custom colors and namespaces. `None` means the entry
+    /// is missing from that theme.
+    Custom {
+        /// Key of the entry in the palette.
+        key: String,
+        /// Previous value.
+        old: Option<PaletteNode>,
+        /// New value.
+        new: Option<PaletteNode>,
+    },
+
+    /// The effects for a palette role changed.
+    Effects {
+        /// Role that changed.
+        role: PaletteColor,
+        /// Previous value.
+        old: EnumSet<Effect>,
+        /// New value.
+        new: EnumSet<Effect>,
+    },
+
+    /// The `effects_enabled` flag changed.
+    EffectsEnabled {
+        /// Previous value.
+        old: bool,
+        /// New value.
+        new: bool,
+    },
+}
+
+impl Theme {
+    /// Returns the differences between this theme and `other`.
+    ///
+    /// `self` is considered the old theme, and `other` the new one. Every
+    /// field is compared except `version`, which does not change how the
+    /// theme looks.
+    ///
+    /// Returns an empty list if both themes look the same.
+    pub fn diff(&self, other: &Theme) -> Vec<ThemeChange> {
+        let mut changes = Vec::new();
+
+        if self.shadow != other.shadow {
+            changes.push(ThemeChange::Shadow {
+                old: self.shadow,
+                new: other.shadow,
+            });
+        }
+
+        if self.shadow_intensity != other.shadow_intensity {
+            changes.push(ThemeChange::ShadowIntensity {
+                old: self.shadow_intensity,
+                new: other.shadow_intensity,
+            });
+        }
+
+        if self.borders != other.borders {
+            changes.push(ThemeChange::Borders {
+                old: self.borders,
+                new: other.borders,
+            });
+        }
+
+        for &role in PaletteColor::all().iter() {
+            let (old, new) = (self.palette[role], other.palette[role]);
+            if old != new {
+                changes.push(ThemeChange::Color { role, old, new });
+            }
+        }
+
+        for (key, old, new) in self.palette.custom_changes(&other.palette) {
+            changes.push(ThemeChange::Custom {
+                key: key.to_string(),
+                old: old.cloned(),
+                new: new.cloned(),
+            });
+        }
+
+        for &role in PaletteColor::all().iter() {
+            let (old, new) = (self.effects[role], other.effects[role]);
+            if old != new {
+                changes.push(ThemeChange::Effects { role, old, new });
+            }
+        }
+
+        if self.effects_enabled != other.effects_enabled {
+            changes.push(ThemeChange::EffectsEnabled {
+                old: self.effects_enabled,
+                new: other.effects_enabled,
+            });
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let old = Theme::default();
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+        new.palette[PaletteColor::Primary] = Color::Rgb(1, 2, 3);
+
+        assert_eq!(
+            old.diff(&new),
+            vec![ThemeChange::Color {
+                role: PaletteColor::Primary,
+                old: old.palette[PaletteColor::Primary],
+                new: Color::Rgb(1, 2, 3),
+            }]
+        );
+
+        new.shadow = false;
+        new.borders = BorderStyle::None;
+        assert_eq!(
+            &old.diff(&new)[..2],
+            &[
+                ThemeChange::Shadow {
+                    old: true,
+                    new: false
+                },
+                ThemeChange::Borders {
+                    old: BorderStyle::Simple,
+                    new: BorderStyle::None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_other_fields() {
+        let old = Theme::default();
+        let mut new = old.clone();
+        new.shadow_intensity = 0.5;
+        new.palette.set_color("accent", Color::Rgb(0, 0, 255));
+        new.effects[PaletteColor::TitlePrimary] = EnumSet::only(Effect::Bold);
+        new.effects_enabled = false;
+        new.version = 2;
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                ThemeChange::ShadowIntensity { old: 1.0, new: 0.5 },
+                ThemeChange::Custom {
+                    key: "accent".to_string(),
+                    old: None,
+                    new: Some(PaletteNode::Color(Color::Rgb(0, 0, 255))),
+                },
+                ThemeChange::Effects {
+                    role: PaletteColor::TitlePrimary,
+                    old: EnumSet::empty(),
+                    new: EnumSet::only(Effect::Bold),
+                },
+                ThemeChange::EffectsEnabled {
+                    old: true,
+                    new: false
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,522 @@
+use super::{load_toml, Effect, Error, PaletteNode, Theme, ThemeChange};
+use enumset::EnumSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use toml_edit::{Document, Item, Table, TableLike, Value};
+
+/// A theme file that keeps its original formatting.
+///
+/// Saving a [`Theme`] directly would lose any comment or formatting from the
+/// original file. A `ThemeDocument` instead keeps the original content, and
+/// only rewrites the values that changed when it is saved.
+///
+/// Every value that can be loaded from a theme file is written back,
+/// including effects and custom palette colors.
+///
+/// Must have the `toml` feature enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive_core::theme::{Color, PaletteColor, ThemeDocument};
+/// let mut document = ThemeDocument::parse(
+///     r#"
+///     [colors]
+///     primary = "black" # Dark text, for readability.
+///     "#,
+/// )
+/// .unwrap();
+///
+/// document.theme_mut().palette[PaletteColor::Primary] = Color::Rgb(0, 0, 51);
+///
+/// let content = document.to_string();
+/// assert!(content
+///     .contains(r##"primary = "#000033" # Dark text, for readability."##));
+/// ```
+///
+/// [`Theme`]: struct.Theme.html
+#[derive(Clone, Debug)]
+pub struct ThemeDocument {
+    document: Document,
+    loaded: Theme,
+    theme: Theme,
+}
+
+impl ThemeDocument {
+    /// Parses a theme document from toml content.
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let theme = load_toml(content)?;
+
+        Ok(ThemeDocument {
+            document: content.parse()?,
+            loaded: theme.clone(),
+            theme,
+        })
+    }
+
+    /// Loads a theme document from the given file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    /// Saves this document to the given file.
+    ///
+    /// The original content is kept, with changed values updated.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Returns the current theme.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Returns a mutable reference to the current theme.
+    ///
+    /// Changes will be written when the document is saved.
+    pub fn theme_mut(&mut self) -> &mut Theme {
+        &mut self.theme
+    }
+}
+
+/// Formats the document content, with updated values.
+impl fmt::Display for ThemeDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut document = self.document.clone();
+        let root = document.as_table_mut();
+
+        if self.loaded.version != self.theme.version {
+            let version = i64::from(self.theme.version);
+            set_value(root, "version", version.into());
+        }
+
+        for change in self.loaded.diff(&self.theme) {
+            match change {
+                ThemeChange::Shadow { new, .. } => {
+                    set_value(root, "shadow", new.into())
+                }
+                ThemeChange::ShadowIntensity { new, .. } => {
+                    // Go through the shortest representation of `new`, to
+                    // write `0.3` rather than `0.30000001192092896`.
+                    let new: f64 = new.to_string().parse().unwrap_or(1.0);
+                    set_value(root, "shadow_intensity", new.into())
+                }
+                ThemeChange::Borders { new, .. } => {
+                    set_value(root, "borders", new.to_string().into())
+                }
+                ThemeChange::Color { role, new, .. } => set_value(
+                    table_mut(root, "colors"),
+                    role.as_str(),
+                    new.to_string().into(),
+                ),
+                ThemeChange::Custom { key, old, new } => set_node(
+                    table_mut(root, "colors"),
+                    &key,
+                    old.as_ref(),
+                    new.as_ref(),
+                ),
+                ThemeChange::Effects { role, new, .. } => {
+                    let effects = table_mut(root, "effects");
+                    if new.is_empty() {
+                        effects.remove(role.as_str());
+                    } else {
+                        set_value(effects, role.as_str(), effects_value(new));
+                    }
+                }
+                ThemeChange::EffectsEnabled { new, .. } => {
+                    set_value(root, "effects_enabled", new.into())
+                }
+            }
+        }
+
+        write!(f, "{}", document)
+    }
+}
+
+/// Returns the table at `key` in `root`, creating it if needed.
+fn table_mut<'a>(root: &'a mut Table, key: &str) -> &'a mut dyn TableLike {
+    let item = root.entry(key).or_insert(toml_edit::table());
+    if !item.is_table_like() {
+        *item = toml_edit::table();
+    }
+    item.as_table_like_mut()
+        .expect("item was just made a table")
+}
+
+/// Sets `key` to `value` in `table`.
+///
+/// Comments around an existing value are kept. New keys are added at the
+/// end of the table, with the same indentation as the other keys.
+fn set_value(table: &mut dyn TableLike, key: &str, mut value: Value) {
+    if let Some(old) = table.get_mut(key).and_then(Item::as_value_mut) {
+        *value.decor_mut() = old.decor().clone();
+        *old = value;
+        return;
+    }
+
+    let last = table
+        .iter()
+        .filter(|(_, item)| item.is_value())
+        .map(|(key, _)| key.to_string())
+        .last();
+    let indent = last.as_deref().and_then(|last| indentation(table, last));
+
+    if let Some(last) = last
+        .as_deref()
+        .and_then(|last| table.get_mut(last))
+        .and_then(Item::as_value_mut)
+    {
+        // In inline tables, the last value has the spacing before the `}`.
+        let suffix = last.decor().suffix().and_then(|suffix| suffix.as_str());
+        if let Some(suffix) = suffix.filter(|s| s.trim().is_empty()) {
+            value.decor_mut().set_suffix(suffix.to_string());
+            last.decor_mut().set_suffix("");
+        }
+    }
+
+    table.insert(key, Item::Value(value));
+    if let (Some(indent), Some(decor)) = (indent, table.key_decor_mut(key)) {
+        decor.set_prefix(indent);
+    }
+}
+
+/// Returns the indentation of `key` in `table`, if any.
+fn indentation(table: &dyn TableLike, key: &str) -> Option<String> {
+    let prefix = table.key_decor(key)?.prefix()?.as_str()?;
+
+    // The prefix also has the comments above the key.
+    let indent = prefix.rsplit('\n').next()?;
+    if indent.trim().is_empty() {
+        Some(indent.to_string())
+    } else {
+        None
+    }
+}
+
+/// Sets the custom palette entry `key` in `table`, from `old` to `new`.
+///
+/// Namespaces already in `table` are updated key by key, to keep their
+/// comments.
+fn set_node(
+    table: &mut dyn TableLike,
+    key: &str,
+    old: Option<&PaletteNode>,
+    new: Option<&PaletteNode>,
+) {
+    let new = match new {
+        Some(new) => new,
+        None => {
+            table.remove(key);
+            return;
+        }
+    };
+
+    if let (Some(PaletteNode::Namespace(old)), PaletteNode::Namespace(new)) =
+        (old, new)
+    {
+        if let Some(namespace) =
+            table.get_mut(key).and_then(Item::as_table_like_mut)
+        {
+            let mut keys: Vec<&String> =
+                old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let (old, new) = (old.get(key), new.get(key));
+                if old != new {
+                    set_node(namespace, key, old, new);
+                }
+            }
+            return;
+        }
+    }
+
+    set_value(table, key, node_value(new));
+}
+
+/// Returns the value describing a custom palette entry.
+fn node_value(node: &PaletteNode) -> Value {
+    match node {
+        PaletteNode::Color(color) => color.to_string().into(),
+        PaletteNode::Namespace(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|&(key, _)| key);
+
+            let table = entries
+                .into_iter()
+                .map(|(key, node)| (key.as_str(), node_value(node)))
+                .collect();
+            Value::InlineTable(table)
+        }
+    }
+}
+
+/// Returns the value describing a set of effects.
+///
+/// A single effect is written as a string, and several as a list.
+fn effects_value(effects: EnumSet<Effect>) -> Value {
+    let mut names = Effect::ordered(effects).map(Effect::as_str);
+    if effects.len() == 1 {
+        names.next().unwrap_or_default().into()
+    } else {
+        Value::Array(names.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{BaseColor, BorderStyle, Color, PaletteColor};
+
+    const CONTENT: &str = r##"# My favorite theme.
+shadow = true # Shadows are nice.
+
+[colors]
+    # Keep the background calm.
+    background = "blue"
+    primary = ["#111111", "black"] # Fallback for the linux TTY.
+
+[effects]
+    title_primary = "bold"
+"##;
+
+    #[test]
+    fn test_unchanged() {
+        let document = ThemeDocument::parse(CONTENT).unwrap();
+        assert_eq!(document.to_string(), CONTENT);
+    }
+
+    #[test]
+    fn test_comments_survive() {
+        let mut document = ThemeDocument::parse(CONTENT).unwrap();
+        let theme = document.theme_mut();
+        theme.shadow = false;
+        theme.borders = BorderStyle::Outset;
+        theme.palette[PaletteColor::Primary] = Color::Light(BaseColor::Blue);
+        theme.palette[PaletteColor::Tertiary] = Color::Rgb(1, 2, 3);
+
+        let content = document.to_string();
+        assert_eq!(
+            content,
+            r##"# My favorite theme.
+shadow = false # Shadows are nice.
+borders = "outset"
+
+[colors]
+    # Keep the background calm.
+    background = "blue"
+    primary = "light blue" # Fallback for the linux TTY.
+    tertiary = "#010203"
+
+[effects]
+    title_primary = "bold"
+"##
+        );
+
+        // The new content describes the updated theme.
+        let reloaded = ThemeDocument::parse(&content).unwrap();
+        assert!(document.theme().diff(reloaded.theme()).is_empty());
+    }
+
+    #[test]
+    fn test_missing_section() {
+        let mut document = ThemeDocument::parse("shadow = false").unwrap();
+        document.theme_mut().palette[PaletteColor::Primary] =
+            Color::Dark(BaseColor::Red);
+
+        assert_eq!(
+            document.to_string(),
+            "shadow = false\n\n[colors]\nprimary = \"red\"\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_line_values() {
+        let content = r##"[colors]
+    primary = [
+        "#111111", # Preferred.
+        "black",
+    ] # Fallback for the linux TTY.
+    secondary = """
+blue"""
+    tertiary = "white"
+"##;
+        let mut document = ThemeDocument::parse(content).unwrap();
+        let theme = document.theme_mut();
+        theme.palette[PaletteColor::Primary] = Color::Rgb(1, 2, 3);
+        theme.palette[PaletteColor::Secondary] = Color::Dark(BaseColor::Red);
+
+        let content = document.to_string();
+        assert_eq!(
+            content,
+            r##"[colors]
+    primary = "#010203" # Fallback for the linux TTY.
+    secondary = "red"
+    tertiary = "white"
+"##
+        );
+
+        let reloaded = ThemeDocument::parse(&content).unwrap();
+        assert!(document.theme().diff(reloaded.theme()).is_empty());
+    }
+
+    #[test]
+    fn test_dotted_keys() {
+        let content = r##"shadow = true
+colors.primary = "black"
+colors."secondary" = "blue" # Links.
+"##;
+        let mut document = ThemeDocument::parse(content).unwrap();
+        let theme = document.theme_mut();
+        theme.palette[PaletteColor::Secondary] = Color::Dark(BaseColor::Red);
+        theme.palette[PaletteColor::Tertiary] = Color::Rgb(1, 2, 3);
+
+        let content = document.to_string();
+        assert_eq!(
+            content,
+            r##"shadow = true
+colors.primary = "black"
+colors."secondary" = "red" # Links.
+colors.tertiary = "#010203"
+"##
+        );
+
+        let reloaded = ThemeDocument::parse(&content).unwrap();
+        assert!(document.theme().diff(reloaded.theme()).is_empty());
+    }
+
+    #[test]
+    fn test_inline_table() {
+        let content = r##"colors = { primary = "black", view = "white" }
+"##;
+        let mut document = ThemeDocument::parse(content).unwrap();
+        let theme = document.theme_mut();
+        theme.palette[PaletteColor::Primary] = Color::Dark(BaseColor::Red);
+        theme.palette[PaletteColor::Tertiary] = Color::Rgb(1, 2, 3);
+
+        let content = document.to_string();
+        assert_eq!(
+            content,
+            r##"colors = { primary = "red", view = "white", tertiary = "#010203" }
+"##
+        );
+
+        let reloaded = ThemeDocument::parse(&content).unwrap();
+        assert!(document.theme().diff(reloaded.theme()).is_empty());
+    }
+
+    #[test]
+    fn test_indented_section() {
+        let content = r##"[colors]
+  primary = "black"
+
+	[effects]
+	title_primary = "bold"
+"##;
+        let mut document = ThemeDocument::parse(content).unwrap();
+        let theme = document.theme_mut();
+        theme.palette[PaletteColor::View] = Color::Dark(BaseColor::Blue);
+        theme.effects[PaletteColor::Primary] = EnumSet::only(Effect::Italic);
+
+        assert_eq!(
+            document.to_string(),
+            r##"[colors]
+  primary = "black"
+  view = "blue"
+
+	[effects]
+	title_primary = "bold"
+	primary = "italic"
+"##
+        );
+    }
+
+    #[test]
+    fn test_other_fields() {
+        let mut document = ThemeDocument::parse(CONTENT).unwrap();
+        let theme = document.theme_mut();
+        theme.shadow_intensity = 0.3;
+        theme.effects_enabled = false;
+        theme.effects[PaletteColor::TitlePrimary] = EnumSet::empty();
+        theme.effects[PaletteColor::Primary] =
+            Effect::Bold | Effect::Underline;
+        theme.palette.set_color("accent", Color::Rgb(0, 0, 255));
+
+        let color = PaletteNode::Color(Color::Dark(BaseColor::Red));
+        let namespace = std::iter::once(("error".to_string(), color));
+        theme.palette.add_namespace("status", namespace.collect());
+
+        let content = document.to_string();
+        assert_eq!(
+            content,
+            r##"# My favorite theme.
+shadow = true # Shadows are nice.
+shadow_intensity = 0.3
+effects_enabled = false
+
+[colors]
+    # Keep the background calm.
+    background = "blue"
+    primary = ["#111111", "black"] # Fallback for the linux TTY.
+    accent = "#0000ff"
+    status = { error = "red" }
+
+[effects]
+    primary = ["bold", "underline"]
+"##
+        );
+
+        let reloaded = ThemeDocument::parse(&content).unwrap();
+        assert!(document.theme().diff(reloaded.theme()).is_empty());
+    }
+
+    #[test]
+    fn test_namespace() {
+        let content = r##"[colors.status]
+error = "red" # Errors stand out.
+warning = "yellow"
+"##;
+        let mut document = ThemeDocument::parse(content).unwrap();
+        let color = PaletteNode::Color(Color::Light(BaseColor::Red));
+        let status = std::iter::once(("error".to_string(), color));
+        document
+            .theme_mut()
+            .palette
+            .add_namespace("status", status.collect());
+
+        let content = document.to_string();
+        assert_eq!(
+            content,
+            r##"[colors.status]
+error = "light red" # Errors stand out.
+"##
+        );
+
+        let reloaded = ThemeDocument::parse(&content).unwrap();
+        assert!(document.theme().diff(reloaded.theme()).is_empty());
+    }
+
+    #[test]
+    fn test_save() {
+        let path = std::env::temp_dir().join(format!(
+            "cursive-theme-document-{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, CONTENT).unwrap();
+
+        let mut document = ThemeDocument::load(&path).unwrap();
+        document.theme_mut().shadow = false;
+        document.save(&path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("# Keep the background calm."));
+        assert!(content.contains("shadow = false # Shadows are nice."));
+        assert!(!ThemeDocument::load(&path).unwrap().theme().shadow);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,7 +1,14 @@
-use enumset::EnumSetType;
+use enumset::{EnumSet, EnumSetType};
+#[cfg(any(feature = "toml", feature = "serde"))]
+use {super::PaletteColor, enum_map::EnumMap, std::str::FromStr};
 
 /// Text effect
 #[derive(EnumSetType, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Effect {
     /// No effect
     Simple,
@@ -16,3 +23,240 @@ pub enum Effect {
     /// Prints foreground with underline
     Underline,
 }
+
+impl Effect {
+    /// Parses an effect from its name, like `"bold"` or `"underline"`.
+    ///
+    /// Parsing is case-insensitive.
+    ///
+    /// Returns `None` if `s` is not a known effect.
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s.to_lowercase().as_str() {
+            "simple" => Effect::Simple,
+            "reverse" => Effect::Reverse,
+            "bold" => Effect::Bold,
+            "italic" => Effect::Italic,
+            "strikethrough" => Effect::Strikethrough,
+            "underline" => Effect::Underline,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if this effect swaps the foreground and background
+    /// colors.
+    ///
+    /// This is only the case for `Effect::Reverse`.
+    pub fn swaps_colors(self) -> bool {
+        self == Effect::Reverse
+    }
+
+    /// Returns `true` if this effect is a text attribute.
+    ///
+    /// Attributes (bold, italic, strikethrough and underline) change how
+    /// characters are printed, but not their colors.
+    pub fn is_attribute(self) -> bool {
+        match self {
+            Effect::Bold
+            | Effect::Italic
+            | Effect::Strikethrough
+            | Effect::Underline => true,
+            Effect::Simple | Effect::Reverse => false,
+        }
+    }
+
+    /// Every effect, in the order they should be applied.
+    ///
+    /// Attributes come first, and `Reverse` last, since it changes the
+    /// colors used by the other effects.
+    const ORDER: [Effect; 6] = [
+        Effect::Simple,
+        Effect::Bold,
+        Effect::Italic,
+        Effect::Underline,
+        Effect::Strikethrough,
+        Effect::Reverse,
+    ];
+
+    /// Returns the effects in `effects`, in the order they should be
+    /// applied.
+    ///
+    /// This order is the same for every backend: `Simple`, `Bold`, `Italic`,
+    /// `Underline`, `Strikethrough`, then `Reverse`.
+    pub fn ordered(effects: EnumSet<Effect>) -> impl Iterator<Item = Effect> {
+        Self::ORDER
+            .iter()
+            .copied()
+            .filter(move |&effect| effects.contains(effect))
+    }
+
+    /// Returns the name of this effect, as used in theme files.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Effect::Simple => "simple",
+            Effect::Reverse => "reverse",
+            Effect::Bold => "bold",
+            Effect::Italic => "italic",
+            Effect::Strikethrough => "strikethrough",
+            Effect::Underline => "underline",
+        }
+    }
+}
+
+/// Fills `effects` with the effects from the given `table`.
+///
+/// Returns a description of every value that could not be loaded.
+#[cfg(feature = "toml")]
+pub(crate) fn load_toml(
+    effects: &mut EnumMap<PaletteColor, EnumSet<Effect>>,
+    table: &toml::value::Table,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for (key, value) in table {
+        let role = match PaletteColor::from_str(key) {
+            Ok(role) => role,
+            Err(()) => {
+                warnings.push(format!("Unknown role `{}` for effects.", key));
+                continue;
+            }
+        };
+
+        // Accept both a single effect and a list of effects.
+        let names: Vec<_> = match value {
+            toml::Value::String(name) => vec![name.as_str()],
+            toml::Value::Array(names) => {
+                names.iter().flat_map(toml::Value::as_str).collect()
+            }
+            _ => {
+                warnings.push(format!("Unexpected effects for `{}`.", key));
+                continue;
+            }
+        };
+
+        let mut set = EnumSet::new();
+        for name in names {
+            match Effect::parse(name) {
+                Some(effect) => {
+                    set.insert(effect);
+                }
+                None => {
+                    log::warn!("Could not parse effect `{}`.", name);
+                    warnings.push(format!(
+                        "Invalid effect for `{}`: `{}`.",
+                        key, name
+                    ));
+                }
+            }
+        }
+        effects[role] = set;
+    }
+
+    warnings
+}
+
+/// Serializes effects per role as a map of role names to effect lists.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_effects {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S>(
+        effects: &EnumMap<PaletteColor, EnumSet<Effect>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(
+            effects.iter().filter(|(_, set)| !set.is_empty()).map(
+                |(role, set)| (role.as_str(), set.iter().collect::<Vec<_>>()),
+            ),
+        )
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<EnumMap<PaletteColor, EnumSet<Effect>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        // Same shapes as in theme files.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawEffects {
+            One(Effect),
+            Many(Vec<Effect>),
+        }
+
+        let mut effects = EnumMap::default();
+        for (key, value) in
+            HashMap::<String, RawEffects>::deserialize(deserializer)?
+        {
+            let role = PaletteColor::from_str(&key).map_err(|()| {
+                D::Error::custom(format!("unknown role `{}`", key))
+            })?;
+            effects[role] = match value {
+                RawEffects::One(effect) => EnumSet::only(effect),
+                RawEffects::Many(list) => list.into_iter().collect(),
+            };
+        }
+        Ok(effects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Effect;
+    use enumset::EnumSet;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Effect::parse("bold"), Some(Effect::Bold));
+        assert_eq!(Effect::parse("underline"), Some(Effect::Underline));
+        assert_eq!(Effect::parse("blink"), None);
+        assert_eq!(Effect::parse(""), None);
+        assert_eq!(Effect::parse("Bold"), Some(Effect::Bold));
+        assert_eq!(Effect::parse("ITALIC"), Some(Effect::Italic));
+    }
+
+    #[test]
+    fn test_ordered() {
+        let effects = Effect::Reverse | Effect::Underline | Effect::Bold;
+        let ordered: Vec<Effect> = Effect::ordered(effects).collect();
+        assert_eq!(
+            ordered,
+            vec![Effect::Bold, Effect::Underline, Effect::Reverse]
+        );
+
+        assert_eq!(Effect::ordered(EnumSet::all()).count(), 6);
+        assert_eq!(Effect::ordered(EnumSet::empty()).next(), None);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let cases = [
+            (Effect::Simple, false, false),
+            (Effect::Reverse, true, false),
+            (Effect::Bold, false, true),
+            (Effect::Italic, false, true),
+            (Effect::Strikethrough, false, true),
+            (Effect::Underline, false, true),
+        ];
+
+        for &(effect, swaps_colors, is_attribute) in &cases {
+            assert_eq!(effect.swaps_colors(), swaps_colors, "{:?}", effect);
+            assert_eq!(effect.is_attribute(), is_attribute, "{:?}", effect);
+        }
+        assert_eq!(cases.len(), EnumSet::<Effect>::all().len());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for effect in EnumSet::<Effect>::all() {
+            assert_eq!(Effect::parse(effect.as_str()), Some(effect));
+        }
+    }
+}
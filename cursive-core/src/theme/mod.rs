@@ -34,13 +34,25 @@
 //! * **`TitlePrimary`**: used to print primary titles.
 //!   Defaults to **red**.
 //! * **`TitleSecondary`**: used to print secondary titles.
-//!   Defaults to **yellow**.
+//!   Defaults to **light blue**.
+//! * **`TitleFocused`**: used to print the title of focused views.
+//!   Defaults to the same color as `TitlePrimary`.
 //! * **`Highlight`**: used to highlight selected items.
 //!   Defaults to **red**.
 //! * **`HighlightInactive`**: used to highlight selected but inactive items.
 //!   Defaults to **blue**.
 //! * **`HighlightText`**: used to print primary text when highlighted
 //!   Defaults to **white**.
+//! * **`RowEven`**: used as background for even rows in lists and tables.
+//!   Defaults to the same color as `View`.
+//! * **`RowOdd`**: used as background for odd rows in lists and tables.
+//!   Defaults to a slightly shaded `View` color.
+//! * **`Error`**: used to print error messages.
+//!   Defaults to **red**.
+//! * **`Warning`**: used to print warnings.
+//!   Defaults to **yellow**.
+//! * **`Success`**: used to print success messages.
+//!   Defaults to **green**.
 //!
 //! A [`Palette`] then maps each of these to an actual [`Color`].
 //!
@@ -94,6 +106,10 @@
 //!   titles.
 //!     * Its *background* color is `View`.
 //!     * Its *foreground* color is `TitleSecondary`.
+//! * **`ColorStyle::title_focused()`**: style used to print the title of
+//!   focused views.
+//!     * Its *background* color is `View`.
+//!     * Its *foreground* color is `TitleFocused`.
 //! * **`ColorStyle::highlight()`**: style used to print selected items.
 //!     * Its *background* color is `Highlight`.
 //!     * Its *foreground* color is `HighlightText`.
@@ -101,6 +117,14 @@
 //!   but inactive items.
 //!     * Its *background* color is `HighlightInactive`.
 //!     * Its *foreground* color is `HighlightText`.
+//! * **`ColorStyle::row_even()`** and **`ColorStyle::row_odd()`**: styles
+//!   used to print alternating rows.
+//!     * Their *background* colors are `RowEven` and `RowOdd`.
+//!     * Their *foreground* color is `Primary`.
+//! * **`ColorStyle::error()`**, **`ColorStyle::warning()`** and
+//!   **`ColorStyle::success()`**: styles used to print status messages.
+//!     * Their *background* color is `View`.
+//!     * Their *foreground* colors are `Error`, `Warning` and `Success`.
 //!
 //! Using one of these pairs when styling your application helps give it a
 //! coherent look.
@@ -154,9 +178,14 @@
 //! ```toml
 //! # Every field in a theme file is optional.
 //!
+//! # Version of the file format. Defaults to 1.
+//! version = 1
+//!
 //! # First come some various options
 //! shadow = false  # Don't draw shadows around stacked views
-//! borders = "simple"  # Alternatives are "none" and "outset"
+//! shadow_intensity = 0.5  # Lighter shadows, between 0.0 and 1.0
+//! borders = "simple"  # Alternatives are "none", "outset", "double" and "rounded"
+//! effects_enabled = false  # Ignore effects like bold or underline
 //!
 //! # Here we define the color palette.
 //! [colors]
@@ -180,6 +209,12 @@
 //!     # Lower precision values can use only 3 digits.
 //!     highlight          = "#F00"
 //!     highlight_inactive = "#5555FF"
+//!
+//! # Effects can be applied to text printed in each role.
+//! [effects]
+//!     title_primary = "bold"
+//!     # Use an array to apply multiple effects.
+//!     highlight     = ["bold", "underline"]
 //! ```
 //!
 //! [`Color`]: ./enum.Color.html
@@ -194,23 +229,50 @@
 //! [theme example]: https://github.com/gyscos/cursive/blob/master/examples/theme.rs
 //! [`Cursive::load_toml`]: ../struct.Cursive.html#method.load_toml
 //! [`theme::load_theme_file`]: ./fn.load_theme_file.html
+mod animated_theme;
 mod border_style;
+mod cache;
 mod color;
+mod color_depth;
 mod color_pair;
 mod color_style;
+mod diff;
+#[cfg(feature = "toml")]
+mod document;
 mod effect;
 mod palette;
+pub mod prelude;
+mod resolved_theme;
+#[cfg(feature = "serde_json")]
+mod scheme;
 mod style;
+#[cfg(feature = "toml")]
+mod theme_file;
+mod validate;
 
-pub use self::border_style::BorderStyle;
+pub use self::animated_theme::AnimatedTheme;
+pub use self::border_style::{BorderChars, BorderStyle};
 pub use self::color::{BaseColor, Color};
+pub use self::color_depth::{detect_color_depth, ColorDepth};
 pub use self::color_pair::ColorPair;
-pub use self::color_style::{ColorStyle, ColorType};
+pub use self::color_style::{resolve_styles, ColorStyle, ColorType};
+pub use self::diff::ThemeChange;
+#[cfg(feature = "toml")]
+pub use self::document::ThemeDocument;
 pub use self::effect::Effect;
-pub use self::palette::{Palette, PaletteColor};
-pub use self::style::Style;
+pub use self::palette::{Colors, Palette, PaletteColor, PaletteNode};
+pub use self::resolved_theme::ResolvedTheme;
+pub use self::style::{Style, StyleStack};
+#[cfg(feature = "toml")]
+pub use self::theme_file::ThemeFile;
+pub use self::validate::ThemeIssue;
+use enum_map::EnumMap;
+use enumset::EnumSet;
+use std::collections::HashMap;
+use std::fmt;
 #[cfg(feature = "toml")]
 use std::fs::File;
+use std::hash::{Hash, Hasher};
 use std::io;
 #[cfg(feature = "toml")]
 use std::io::Read;
@@ -218,40 +280,600 @@ use std::io::Read;
 use std::path::Path;
 
 /// Represents the style a Cursive application will use.
+///
+/// With the `serde` feature, it uses the same layout as theme files.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct Theme {
+    /// Version of the theme file format this theme was loaded from.
+    ///
+    /// Theme files without a `version` key use version 1.
+    pub version: u32,
     /// Whether views in a StackView should have shadows.
     pub shadow: bool,
+    /// How strong shadows are, between `0.0` and `1.0`.
+    ///
+    /// Shadows are drawn with the `Shadow` color mixed over the `Background`
+    /// color: `1.0` uses the `Shadow` color as-is, while `0.0` makes shadows
+    /// invisible. See [`Theme::shadow_style`].
+    ///
+    /// [`Theme::shadow_style`]: #method.shadow_style
+    pub shadow_intensity: f32,
     /// How view borders should be drawn.
     pub borders: BorderStyle,
     /// What colors should be used through the application?
+    #[cfg_attr(feature = "serde", serde(rename = "colors"))]
     pub palette: Palette,
+    /// Effects that should be applied to text printed in each role.
+    ///
+    /// For example, setting `Bold` for `TitlePrimary` requests bold titles.
+    #[cfg_attr(feature = "serde", serde(with = "effect::serde_effects"))]
+    pub effects: EnumMap<PaletteColor, EnumSet<Effect>>,
+    /// Whether effects like bold or underline should be used at all.
+    ///
+    /// When `false`, text is printed without any effect. This can help with
+    /// accessibility, or with terminals that render effects poorly. See
+    /// [`Theme::effective_effect`].
+    ///
+    /// [`Theme::effective_effect`]: #method.effective_effect
+    pub effects_enabled: bool,
 }
 
 impl Default for Theme {
     fn default() -> Self {
         Theme {
+            version: Theme::VERSION,
             shadow: true,
+            shadow_intensity: 1.0,
             borders: BorderStyle::Simple,
             palette: Palette::default(),
+            effects: EnumMap::default(),
+            effects_enabled: true,
         }
     }
 }
 
+// `shadow_intensity` is compared by its bits, so `Theme` can be `Eq`.
+impl PartialEq for Theme {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+            && self.shadow == other.shadow
+            && self.shadow_intensity.to_bits()
+                == other.shadow_intensity.to_bits()
+            && self.borders == other.borders
+            && self.palette == other.palette
+            && self.effects == other.effects
+            && self.effects_enabled == other.effects_enabled
+    }
+}
+
+impl Eq for Theme {}
+
+impl Hash for Theme {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.version.hash(state);
+        self.shadow.hash(state);
+        self.shadow_intensity.to_bits().hash(state);
+        self.borders.hash(state);
+        self.palette.hash(state);
+        self.effects.hash(state);
+        self.effects_enabled.hash(state);
+    }
+}
+
 impl Theme {
+    /// Latest version of the theme file format supported.
+    pub const VERSION: u32 = 1;
+
+    /// Returns a flat theme, without shadows.
+    ///
+    /// This uses the default palette and simple borders. Skipping shadows
+    /// makes redraws cheaper when many views are stacked.
+    pub fn flat() -> Theme {
+        Theme {
+            shadow: false,
+            borders: BorderStyle::Simple,
+            ..Theme::default()
+        }
+    }
+
+    /// Returns a minimal theme, without shadows or borders.
+    pub fn minimal() -> Theme {
+        Theme {
+            shadow: false,
+            borders: BorderStyle::None,
+            ..Theme::default()
+        }
+    }
+
+    /// Returns the names of the built-in themes available with
+    /// [`Theme::named`].
+    ///
+    /// [`Theme::named`]: #method.named
+    pub fn preset_names() -> &'static [&'static str] {
+        &["dark", "light", "solarized"]
+    }
+
+    /// Returns a built-in theme by name.
+    ///
+    /// Returns `None` if no such theme exists. See [`Theme::preset_names`]
+    /// for the list of available themes.
+    ///
+    /// [`Theme::preset_names`]: #method.preset_names
+    pub fn named(name: &str) -> Option<Theme> {
+        use self::BaseColor::*;
+        use self::Color::*;
+        use self::PaletteColor::*;
+
+        let colors = match name {
+            "dark" => [
+                (Background, Dark(Black)),
+                (Shadow, Dark(Black)),
+                (View, Light(Black)),
+                (Primary, Light(White)),
+                (Secondary, Light(Blue)),
+                (Tertiary, Dark(White)),
+                (TitlePrimary, Light(Yellow)),
+                (TitleSecondary, Light(Cyan)),
+                (TitleFocused, Light(Yellow)),
+                (Highlight, Dark(Cyan)),
+                (HighlightInactive, Dark(Blue)),
+                (HighlightText, Dark(Black)),
+                (Error, Light(Red)),
+                (Warning, Light(Yellow)),
+                (Success, Light(Green)),
+            ],
+            "light" => [
+                (Background, Light(White)),
+                (Shadow, Dark(White)),
+                (View, Light(White)),
+                (Primary, Dark(Black)),
+                (Secondary, Dark(Blue)),
+                (Tertiary, Light(Black)),
+                (TitlePrimary, Dark(Blue)),
+                (TitleSecondary, Dark(Cyan)),
+                (TitleFocused, Dark(Blue)),
+                (Highlight, Dark(Blue)),
+                (HighlightInactive, Dark(White)),
+                (HighlightText, Light(White)),
+                (Error, Dark(Red)),
+                (Warning, Dark(Yellow)),
+                (Success, Dark(Green)),
+            ],
+            "solarized" => [
+                (Background, Rgb(0x00, 0x2b, 0x36)),
+                (Shadow, Dark(Black)),
+                (View, Rgb(0x07, 0x36, 0x42)),
+                (Primary, Rgb(0x83, 0x94, 0x96)),
+                (Secondary, Rgb(0x93, 0xa1, 0xa1)),
+                (Tertiary, Rgb(0x58, 0x6e, 0x75)),
+                (TitlePrimary, Rgb(0x26, 0x8b, 0xd2)),
+                (TitleSecondary, Rgb(0x2a, 0xa1, 0x98)),
+                (TitleFocused, Rgb(0x26, 0x8b, 0xd2)),
+                (Highlight, Rgb(0xb5, 0x89, 0x00)),
+                (HighlightInactive, Rgb(0x58, 0x6e, 0x75)),
+                (HighlightText, Rgb(0x00, 0x2b, 0x36)),
+                (Error, Rgb(0xdc, 0x32, 0x2f)),
+                (Warning, Rgb(0xb5, 0x89, 0x00)),
+                (Success, Rgb(0x85, 0x99, 0x00)),
+            ],
+            _ => return None,
+        };
+
+        let mut theme = Theme::default();
+        theme.palette.extend(colors.iter().cloned());
+        theme.palette.update_rows();
+        Some(theme)
+    }
+
+    /// Creates a theme derived from a single accent color.
+    ///
+    /// The accent is used for highlights and titles, while the other colors
+    /// are picked to contrast with it: a light view for dark accents, and a
+    /// dark view for light ones.
+    pub fn from_accent(accent: Color) -> Theme {
+        use self::PaletteColor::*;
+
+        let (view, primary) = if accent.luminance() < 0.179 {
+            (accent.lighten(0.9), accent.darken(0.85))
+        } else {
+            (accent.darken(0.85), accent.lighten(0.9))
+        };
+
+        // Use whichever of the text or view colors stands out the most.
+        let highlight_text =
+            if view.contrast_ratio(accent) > primary.contrast_ratio(accent) {
+                view
+            } else {
+                primary
+            };
+
+        let mut theme = Theme::default();
+        theme.palette.extend(vec![
+            (Background, accent.darken(0.6)),
+            (Shadow, accent.darken(0.9)),
+            (View, view),
+            (Primary, primary),
+            (Secondary, primary.mix(accent, 0.4)),
+            (Tertiary, primary.mix(view, 0.5)),
+            (TitlePrimary, accent),
+            (TitleSecondary, accent.mix(primary, 0.4)),
+            (TitleFocused, accent),
+            (Highlight, accent),
+            (HighlightInactive, accent.mix(view, 0.5)),
+            (HighlightText, highlight_text),
+        ]);
+        theme.palette.update_rows();
+        theme
+    }
+
+    /// Returns a copy of this theme, with colors that can be displayed with
+    /// the given color depth.
+    ///
+    /// Each palette color is replaced with its [downgraded] version.
+    ///
+    /// [downgraded]: ./enum.Color.html#method.downgrade
+    pub fn downgrade(&self, depth: ColorDepth) -> Theme {
+        Theme {
+            palette: self.palette.map_colors(|color| color.downgrade(depth)),
+            ..self.clone()
+        }
+    }
+
+    /// Loads a theme from the file named by the environment variable `var`.
+    ///
+    /// If the variable is not set, the default theme is returned. Otherwise,
+    /// the file is loaded with [`load_theme_file`], and any error is
+    /// returned.
+    ///
+    /// Must have the `toml` feature enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use cursive_core::theme::Theme;
+    /// // Let users pick a theme with `MY_APP_THEME=path/to/theme.toml`.
+    /// let theme = Theme::from_env("MY_APP_THEME").unwrap();
+    /// ```
+    ///
+    /// [`load_theme_file`]: ./fn.load_theme_file.html
     #[cfg(feature = "toml")]
-    fn load_toml(&mut self, table: &toml::value::Table) {
+    pub fn from_env(var: &str) -> Result<Theme, Error> {
+        match std::env::var_os(var) {
+            Some(path) => load_theme_file(path),
+            None => Ok(Theme::default()),
+        }
+    }
+
+    /// Returns a copy of this theme with inverted colors.
+    ///
+    /// Each RGB component of every palette color is inverted, which roughly
+    /// swaps light and dark colors. Base colors are inverted using their RGB
+    /// value (see [`Color::to_rgb`]), then replaced with the closest base
+    /// color. `Color::TerminalDefault` is unchanged.
+    ///
+    /// [`Color::to_rgb`]: ./enum.Color.html#method.to_rgb
+    pub fn invert(&self) -> Theme {
+        let invert = |color: Color| match color {
+            Color::TerminalDefault => color,
+            Color::RgbLowRes(r, g, b) => Color::RgbLowRes(5 - r, 5 - g, 5 - b),
+            color => {
+                let (r, g, b) = color.to_rgb().unwrap_or((0, 0, 0));
+                let inverted = Color::Rgb(255 - r, 255 - g, 255 - b);
+                match color {
+                    Color::Dark(_) | Color::Light(_) => {
+                        inverted.downgrade(ColorDepth::Basic16)
+                    }
+                    _ => inverted,
+                }
+            }
+        };
+
+        Theme {
+            palette: self.palette.map_colors(invert),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this theme adapted to the current terminal.
+    ///
+    /// This downgrades the theme to the color depth guessed by
+    /// [`detect_color_depth`].
+    ///
+    /// [`detect_color_depth`]: ./fn.detect_color_depth.html
+    pub fn for_current_terminal(&self) -> Theme {
+        self.downgrade(detect_color_depth())
+    }
+
+    /// Returns the style used to draw shadows.
+    ///
+    /// With a `shadow_intensity` of `1.0` (the default), this is
+    /// `ColorStyle::shadow()`. Otherwise, the `Shadow` color is mixed over
+    /// the `Background` color according to the intensity (see
+    /// [`Color::mix`]).
+    ///
+    /// [`Color::mix`]: ./enum.Color.html#method.mix
+    pub fn shadow_style(&self) -> ColorStyle {
+        if self.shadow_intensity >= 1.0 {
+            return ColorStyle::shadow();
+        }
+
+        let color = self.palette[PaletteColor::Background]
+            .mix(self.palette[PaletteColor::Shadow], self.shadow_intensity);
+        ColorStyle::new(color, color)
+    }
+
+    /// Returns the effect to actually use instead of `effect`.
+    ///
+    /// This is `effect` itself, or `Effect::Simple` when
+    /// [`effects_enabled`] is `false`.
+    ///
+    /// [`effects_enabled`]: #structfield.effects_enabled
+    pub fn effective_effect(&self, effect: Effect) -> Effect {
+        if self.effects_enabled {
+            effect
+        } else {
+            Effect::Simple
+        }
+    }
+
+    /// Mixes `color` toward the `View` color of this theme.
+    ///
+    /// `amount` is the weight of the `View` color, between `0.0` (returns
+    /// `color`) and `1.0` (returns the `View` color). This can be used to
+    /// derive states, like hovered items, that match the palette.
+    ///
+    /// See [`Color::mix`].
+    ///
+    /// [`Color::mix`]: ./enum.Color.html#method.mix
+    pub fn tint(&self, color: Color, amount: f32) -> Color {
+        color.mix(self.palette[PaletteColor::View], amount)
+    }
+
+    /// Returns `true` if this theme has a dark look.
+    ///
+    /// This looks at the `View` color, used as background for most text.
+    /// The theme is considered dark if the [relative luminance] of this
+    /// color is below `0.179`, where black text and white text would have
+    /// the same contrast on it.
+    ///
+    /// [relative luminance]: ./enum.Color.html#method.luminance
+    pub fn is_dark(&self) -> bool {
+        self.palette[PaletteColor::View].luminance() < 0.179
+    }
+
+    /// Returns a textual preview of this theme.
+    ///
+    /// Each built-in [`ColorStyle`] is listed on its own line, with its
+    /// resolved colors, like `primary: #000000 on #aaaaaa`. Colors using the
+    /// terminal default are shown as `default`.
+    ///
+    /// [`ColorStyle`]: ./struct.ColorStyle.html
+    pub fn preview(&self) -> String {
+        const NAMES: [&str; 16] = [
+            "terminal_default",
+            "background",
+            "shadow",
+            "primary",
+            "secondary",
+            "tertiary",
+            "title_primary",
+            "title_secondary",
+            "title_focused",
+            "highlight",
+            "highlight_inactive",
+            "row_even",
+            "row_odd",
+            "error",
+            "warning",
+            "success",
+        ];
+
+        let hex = |color: Color| {
+            color
+                .to_hex_string()
+                .unwrap_or_else(|| "default".to_string())
+        };
+
+        NAMES
+            .iter()
+            .zip(ColorStyle::all())
+            .map(|(name, style)| {
+                let pair = style.resolve(&self.palette);
+                format!(
+                    "{}: {} on {}\n",
+                    name,
+                    hex(pair.front),
+                    hex(pair.back)
+                )
+            })
+            .collect()
+    }
+
+    /// Applies overrides given as a map of keys to string values.
+    ///
+    /// Keys use the same names as theme files: `shadow` (`"true"` or
+    /// `"false"`), `borders` (see [`BorderStyle::parse`]), and
+    /// `colors.<role>` for basic palette colors (see [`Color::parse`]).
+    ///
+    /// Returns the keys that could not be applied, sorted, either because
+    /// the key is unknown or because the value is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive_core::theme::{Color, PaletteColor, Theme};
+    /// # use std::collections::HashMap;
+    /// let mut overrides = HashMap::new();
+    /// overrides.insert("shadow".to_string(), "false".to_string());
+    /// overrides.insert("colors.primary".to_string(), "#ff0000".to_string());
+    ///
+    /// let mut theme = Theme::default();
+    /// assert!(theme.apply_overrides(&overrides).is_empty());
+    /// assert!(!theme.shadow);
+    /// assert_eq!(
+    ///     theme.palette[PaletteColor::Primary],
+    ///     Color::Rgb(255, 0, 0)
+    /// );
+    /// ```
+    ///
+    /// [`BorderStyle::parse`]: ./enum.BorderStyle.html#method.parse
+    /// [`Color::parse`]: ./enum.Color.html#method.parse
+    pub fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Vec<String> {
+        let mut failed: Vec<String> = overrides
+            .iter()
+            .filter(|&(key, value)| !self.apply_override(key, value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        failed.sort();
+        failed
+    }
+
+    // Returns `false` if the override could not be applied.
+    fn apply_override(&mut self, key: &str, value: &str) -> bool {
+        match key {
+            "shadow" => match value.parse() {
+                Ok(shadow) => self.shadow = shadow,
+                Err(_) => return false,
+            },
+            "borders" => match BorderStyle::parse(value) {
+                Some(borders) => self.borders = borders,
+                None => return false,
+            },
+            _ => {
+                let role = match key.strip_prefix("colors.") {
+                    Some(role) => role,
+                    None => return false,
+                };
+                return match Color::parse(value) {
+                    Some(color) => self.palette.set(role, color),
+                    None => false,
+                };
+            }
+        }
+        true
+    }
+
+    // Returns a description of every value that could not be loaded.
+    #[cfg(feature = "toml")]
+    fn load_toml(
+        &mut self,
+        table: &toml::value::Table,
+        depth: ColorDepth,
+    ) -> Vec<String> {
+        let mut warnings = Vec::new();
+
         if let Some(&toml::Value::Boolean(shadow)) = table.get("shadow") {
             self.shadow = shadow;
         }
 
+        let intensity = match table.get("shadow_intensity") {
+            Some(&toml::Value::Float(intensity)) => Some(intensity),
+            Some(&toml::Value::Integer(intensity)) => Some(intensity as f64),
+            _ => None,
+        };
+        match intensity {
+            Some(intensity) if intensity.is_finite() => {
+                self.shadow_intensity = (intensity as f32).clamp(0.0, 1.0);
+            }
+            Some(intensity) => {
+                log::warn!("Invalid shadow intensity `{}`.", intensity);
+                warnings.push(format!(
+                    "Invalid shadow intensity `{}`.",
+                    intensity
+                ));
+            }
+            None => (),
+        }
+
+        if let Some(&toml::Value::Boolean(enabled)) =
+            table.get("effects_enabled")
+        {
+            self.effects_enabled = enabled;
+        }
+
         if let Some(&toml::Value::String(ref borders)) = table.get("borders") {
-            self.borders = BorderStyle::from(borders);
+            match BorderStyle::parse(borders) {
+                Some(borders) => self.borders = borders,
+                None => {
+                    log::warn!("Invalid border style `{}`.", borders);
+                    warnings
+                        .push(format!("Invalid border style `{}`.", borders));
+                }
+            }
         }
 
         if let Some(&toml::Value::Table(ref table)) = table.get("colors") {
-            palette::load_toml(&mut self.palette, table);
+            warnings.extend(palette::load_toml(
+                &mut self.palette,
+                table,
+                depth,
+            ));
+        }
+
+        if let Some(toml::Value::Table(table)) = table.get("effects") {
+            warnings.extend(effect::load_toml(&mut self.effects, table));
         }
+
+        warnings
+    }
+}
+
+/// Builds a [`Theme`] by overriding values from the default theme.
+///
+/// [`Theme`]: ./struct.Theme.html
+///
+/// # Example
+///
+/// ```rust
+/// # use cursive_core::theme::{BorderStyle, Color, PaletteColor, ThemeBuilder};
+/// let theme = ThemeBuilder::new()
+///     .shadow(false)
+///     .borders(BorderStyle::Outset)
+///     .color(PaletteColor::View, Color::Rgb(211, 215, 207))
+///     .build();
+///
+/// assert!(!theme.shadow);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ThemeBuilder {
+    theme: Theme,
+}
+
+impl ThemeBuilder {
+    /// Creates a new builder, starting from the default theme.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether views in a StackView should have shadows.
+    pub fn shadow(mut self, shadow: bool) -> Self {
+        self.theme.shadow = shadow;
+        self
+    }
+
+    /// Sets how view borders should be drawn.
+    pub fn borders(mut self, borders: BorderStyle) -> Self {
+        self.theme.borders = borders;
+        self
+    }
+
+    /// Sets the color used for the given palette role.
+    pub fn color(mut self, role: PaletteColor, color: Color) -> Self {
+        self.theme.palette[role] = color;
+        self
+    }
+
+    /// Returns the built theme.
+    pub fn build(self) -> Theme {
+        self.theme
     }
 }
 
@@ -264,6 +886,71 @@ pub enum Error {
     #[cfg(feature = "toml")]
     /// An error occured when parsing the toml content.
     Parse(toml::de::Error),
+
+    #[cfg(feature = "toml")]
+    /// An error occured when parsing the toml content of a
+    /// [`ThemeDocument`](struct.ThemeDocument.html).
+    Document(toml_edit::TomlError),
+
+    /// An error occured when decoding a theme from bytes.
+    ///
+    /// See [`Theme::from_bytes`](struct.Theme.html#method.from_bytes).
+    Decode(String),
+
+    #[cfg(feature = "toml")]
+    /// The theme uses a version of the file format that is not supported.
+    ///
+    /// Contains the `version` value from the theme.
+    UnsupportedVersion(String),
+
+    #[cfg(feature = "serde_json")]
+    /// An error occured when parsing a JSON color scheme.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "could not read theme: {}", err),
+            Error::Decode(reason) => {
+                write!(f, "could not decode theme: {}", reason)
+            }
+            #[cfg(feature = "toml")]
+            Error::Parse(err) => write!(f, "could not parse theme: {}", err),
+            #[cfg(feature = "toml")]
+            Error::Document(err) => {
+                write!(f, "could not parse theme document: {}", err)
+            }
+            #[cfg(feature = "toml")]
+            Error::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported theme version: {} (latest supported: {})",
+                version,
+                Theme::VERSION
+            ),
+            #[cfg(feature = "serde_json")]
+            Error::Json(err) => {
+                write!(f, "could not parse color scheme: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Decode(_) => None,
+            #[cfg(feature = "toml")]
+            Error::Parse(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Error::Document(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Error::UnsupportedVersion(_) => None,
+            #[cfg(feature = "serde_json")]
+            Error::Json(err) => Some(err),
+        }
+    }
 }
 
 #[cfg(feature = "toml")]
@@ -280,6 +967,20 @@ impl From<toml::de::Error> for Error {
     }
 }
 
+#[cfg(feature = "toml")]
+impl From<toml_edit::TomlError> for Error {
+    fn from(err: toml_edit::TomlError) -> Self {
+        Error::Document(err)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
 #[cfg(feature = "toml")]
 /// Loads a theme from file.
 ///
@@ -295,20 +996,1172 @@ pub fn load_theme_file<P: AsRef<Path>>(filename: P) -> Result<Theme, Error> {
     load_toml(&content)
 }
 
+/// Loads several theme files, each overriding the previous ones.
+///
+/// Files are applied in order, starting from the default theme. This can be
+/// used for layered configuration, like a system theme, then a user theme,
+/// then a project theme.
+///
+/// If `skip_missing` is `true`, files that do not exist are ignored.
+/// Otherwise, they cause an `Error::Io`.
+///
+/// Must have the `toml` feature enabled.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use cursive_core::theme::load_theme_files;
+/// let theme = load_theme_files(
+///     &["/etc/my_app/theme.toml", "/home/user/.config/my_app/theme.toml"],
+///     true,
+/// )
+/// .unwrap();
+/// ```
+#[cfg(feature = "toml")]
+pub fn load_theme_files<P: AsRef<Path>>(
+    paths: &[P],
+    skip_missing: bool,
+) -> Result<Theme, Error> {
+    let mut theme = Theme::default();
+
+    for path in paths {
+        let content = match std::fs::read_to_string(path) {
+            Err(err)
+                if skip_missing && err.kind() == io::ErrorKind::NotFound =>
+            {
+                continue
+            }
+            content => content?,
+        };
+        theme = load_toml_onto(&content, theme)?;
+    }
+
+    Ok(theme)
+}
+
 /// Loads a theme string and sets it as active.
 ///
 /// Must have the `toml` feature enabled.
 #[cfg(feature = "toml")]
 pub fn load_toml(content: &str) -> Result<Theme, Error> {
-    let table = toml::de::from_str(content)?;
+    load_toml_onto(content, Theme::default())
+}
+
+/// Loads a theme string on top of the given base theme.
+///
+/// Only the values present in `content` will override those from `base`.
+///
+/// Must have the `toml` feature enabled.
+#[cfg(feature = "toml")]
+pub fn load_toml_onto(content: &str, base: Theme) -> Result<Theme, Error> {
+    load_toml_table(content, base, ColorDepth::TrueColor)
+        .map(|(theme, _)| theme)
+}
+
+/// Loads a theme string, picking colors for the given color depth.
+///
+/// Colors in the theme can be given for each color depth, using a table
+/// with `truecolor`, `palette256`, `basic16`, `basic8` and `basic` keys:
+///
+/// ```toml
+/// [colors]
+///     primary = { truecolor = "#123456", basic = "blue" }
+/// ```
+///
+/// The entry for `depth` is used if present, otherwise the next most
+/// compatible one. Other loading functions use the `truecolor` entry.
+///
+/// Must have the `toml` feature enabled.
+#[cfg(feature = "toml")]
+pub fn load_toml_with_depth(
+    content: &str,
+    depth: ColorDepth,
+) -> Result<Theme, Error> {
+    load_toml_table(content, Theme::default(), depth).map(|(theme, _)| theme)
+}
+
+/// Loads a theme string, and reports the values that could not be loaded.
+///
+/// Invalid values (like a misspelled color) do not prevent loading the rest
+/// of the theme: they are skipped, and described in the returned warnings.
+///
+/// Must have the `toml` feature enabled.
+#[cfg(feature = "toml")]
+pub fn load_toml_with_warnings(
+    content: &str,
+) -> Result<(Theme, Vec<String>), Error> {
+    load_toml_table(content, Theme::default(), ColorDepth::TrueColor)
+}
+
+/// Loads a theme string, failing on any unknown or invalid value.
+///
+/// Unlike [`load_toml`], which skips them, this reports every problem found
+/// in the theme: unknown top-level keys, unknown colors in the `[colors]`
+/// table, and any value that [`load_toml_with_warnings`] would warn about.
+/// Custom colors are only accepted inside a namespace table.
+///
+/// This can be used to check theme files, for example in CI.
+///
+/// Must have the `toml` feature enabled.
+///
+/// [`load_toml`]: ./fn.load_toml.html
+/// [`load_toml_with_warnings`]: ./fn.load_toml_with_warnings.html
+#[cfg(feature = "toml")]
+pub fn load_toml_strict(content: &str) -> Result<Theme, Vec<String>> {
+    const KEYS: [&str; 7] = [
+        "version",
+        "shadow",
+        "shadow_intensity",
+        "borders",
+        "colors",
+        "effects",
+        "effects_enabled",
+    ];
+
+    let table: toml::value::Table =
+        toml::de::from_str(content).map_err(|err| vec![err.to_string()])?;
+
+    let mut errors: Vec<String> = table
+        .keys()
+        .filter(|key| !KEYS.contains(&key.as_str()))
+        .map(|key| format!("Unknown key `{}`.", key))
+        .collect();
+
+    if let Some(toml::Value::Table(colors)) = table.get("colors") {
+        errors.extend(palette::unknown_keys(colors));
+    }
 
     let mut theme = Theme::default();
-    theme.load_toml(&table);
+    match toml_version(&table) {
+        Ok(version) => theme.version = version,
+        Err(err) => errors.push(err.to_string()),
+    }
+    errors.extend(theme.load_toml(&table, ColorDepth::TrueColor));
 
-    Ok(theme)
+    if errors.is_empty() {
+        Ok(theme)
+    } else {
+        Err(errors)
+    }
+}
+
+// Returns the version of the theme file format used by `table`.
+#[cfg(feature = "toml")]
+fn toml_version(table: &toml::value::Table) -> Result<u32, Error> {
+    match table.get("version") {
+        None => Ok(1),
+        Some(&toml::Value::Integer(version))
+            if version >= 1 && version <= i64::from(Theme::VERSION) =>
+        {
+            Ok(version as u32)
+        }
+        Some(version) => Err(Error::UnsupportedVersion(version.to_string())),
+    }
+}
+
+#[cfg(feature = "toml")]
+fn load_toml_table(
+    content: &str,
+    base: Theme,
+    depth: ColorDepth,
+) -> Result<(Theme, Vec<String>), Error> {
+    // A theme file is always a table at the top level: anything else is
+    // reported as a parse error.
+    let table: toml::value::Table = toml::de::from_str(content)?;
+
+    let mut theme = base;
+    theme.version = toml_version(&table)?;
+    let warnings = theme.load_toml(&table, depth);
+
+    Ok((theme, warnings))
 }
 
 /// Loads the default theme, and returns its representation.
 pub fn load_default() -> Theme {
     Theme::default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(Theme::default());
+        set.insert(Theme::default());
+        assert_eq!(set.len(), 1);
+
+        let dark = Theme::named("dark").unwrap();
+        set.insert(dark.clone());
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&dark));
+    }
+
+    #[test]
+    fn test_builder() {
+        let theme = ThemeBuilder::new()
+            .shadow(false)
+            .borders(BorderStyle::None)
+            .color(PaletteColor::Primary, Color::Rgb(1, 2, 3))
+            .build();
+
+        let mut expected = Theme {
+            shadow: false,
+            borders: BorderStyle::None,
+            ..Theme::default()
+        };
+        expected.palette[PaletteColor::Primary] = Color::Rgb(1, 2, 3);
+
+        assert_eq!(theme.shadow, expected.shadow);
+        assert_eq!(theme.borders, expected.borders);
+        assert_eq!(theme.palette, expected.palette);
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let overrides: HashMap<String, String> = vec![
+            ("shadow", "false"),
+            ("borders", "outset"),
+            ("colors.view", "light blue"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+        let mut theme = Theme::default();
+        assert!(theme.apply_overrides(&overrides).is_empty());
+        assert!(!theme.shadow);
+        assert_eq!(theme.borders, BorderStyle::Outset);
+        assert_eq!(
+            theme.palette[PaletteColor::View],
+            Color::Light(BaseColor::Blue)
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides_invalid() {
+        let overrides: HashMap<String, String> = vec![
+            ("shadow", "maybe"),
+            ("borders", "wavy"),
+            ("colors.view", "not a color"),
+            ("colors.not_a_role", "red"),
+            ("primary", "red"),
+            ("colors.primary", "red"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+        let mut theme = Theme::default();
+        assert_eq!(
+            theme.apply_overrides(&overrides),
+            vec![
+                "borders",
+                "colors.not_a_role",
+                "colors.view",
+                "primary",
+                "shadow",
+            ]
+        );
+
+        // Valid overrides are still applied.
+        assert_eq!(
+            theme.palette[PaletteColor::Primary],
+            Color::Dark(BaseColor::Red)
+        );
+        assert_eq!(theme.shadow, Theme::default().shadow);
+    }
+
+    #[test]
+    fn test_shadow_intensity() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::Background] = Color::Rgb(200, 200, 200);
+        theme.palette[PaletteColor::Shadow] = Color::Rgb(0, 0, 0);
+
+        assert_eq!(theme.shadow_style(), ColorStyle::shadow());
+
+        theme.shadow_intensity = 0.0;
+        let pair = theme.shadow_style().resolve(&theme.palette);
+        assert_eq!(pair.front, Color::Rgb(200, 200, 200));
+        assert_eq!(pair.back, Color::Rgb(200, 200, 200));
+
+        theme.shadow_intensity = 0.5;
+        let pair = theme.shadow_style().resolve(&theme.palette);
+        assert_eq!(pair.back, Color::Rgb(100, 100, 100));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_shadow_intensity() {
+        let theme = load_toml("shadow_intensity = 0.25").unwrap();
+        assert_eq!(theme.shadow_intensity, 0.25);
+
+        let theme = load_toml("shadow_intensity = 4.0").unwrap();
+        assert_eq!(theme.shadow_intensity, 1.0);
+
+        let theme = load_toml("shadow_intensity = 0").unwrap();
+        assert_eq!(theme.shadow_intensity, 0.0);
+        let theme = load_toml("shadow_intensity = 1").unwrap();
+        assert_eq!(theme.shadow_intensity, 1.0);
+        let theme = load_toml("shadow_intensity = 3").unwrap();
+        assert_eq!(theme.shadow_intensity, 1.0);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_shadow_intensity_not_finite() {
+        for value in &["nan", "inf", "-inf"] {
+            let (theme, warnings) = load_toml_with_warnings(&format!(
+                "shadow_intensity = {}",
+                value
+            ))
+            .unwrap();
+            assert_eq!(theme.shadow_intensity, 1.0);
+            assert_eq!(warnings.len(), 1, "{}", value);
+            assert!(warnings[0].contains("shadow intensity"));
+        }
+    }
+
+    #[test]
+    fn test_effective_effect() {
+        let mut theme = Theme::default();
+        assert!(theme.effects_enabled);
+        assert_eq!(theme.effective_effect(Effect::Bold), Effect::Bold);
+
+        theme.effects_enabled = false;
+        assert_eq!(theme.effective_effect(Effect::Bold), Effect::Simple);
+        assert_eq!(theme.effective_effect(Effect::Reverse), Effect::Simple);
+        assert_ne!(theme, Theme::default());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_effects_enabled() {
+        let theme = load_toml("effects_enabled = false").unwrap();
+        assert!(!theme.effects_enabled);
+        assert!(load_toml("").unwrap().effects_enabled);
+        assert!(load_toml_strict("effects_enabled = false").is_ok());
+    }
+
+    #[test]
+    fn test_tint() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::View] = Color::Rgb(255, 255, 255);
+
+        let color = Color::Rgb(0, 0, 100);
+        assert_eq!(theme.tint(color, 0.0), color);
+        assert_eq!(theme.tint(color, 0.5), Color::Rgb(128, 128, 178));
+        assert_eq!(theme.tint(color, 1.0), Color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_is_dark() {
+        let theme = ThemeBuilder::new()
+            .color(PaletteColor::View, Color::Rgb(0x1e, 0x1e, 0x1e))
+            .build();
+        assert!(theme.is_dark());
+
+        let theme = ThemeBuilder::new()
+            .color(PaletteColor::View, Color::Rgb(0xfd, 0xf6, 0xe3))
+            .build();
+        assert!(!theme.is_dark());
+
+        // The default view color is a light gray.
+        assert!(!Theme::default().is_dark());
+    }
+
+    #[test]
+    fn test_presets() {
+        let flat = Theme::flat();
+        assert!(!flat.shadow);
+        assert_eq!(flat.borders, BorderStyle::Simple);
+        assert_eq!(flat.palette, Palette::default());
+
+        let minimal = Theme::minimal();
+        assert!(!minimal.shadow);
+        assert_eq!(minimal.borders, BorderStyle::None);
+        assert_eq!(minimal.palette, Palette::default());
+    }
+
+    #[test]
+    fn test_named() {
+        for name in Theme::preset_names() {
+            let theme = Theme::named(name).unwrap();
+            assert_ne!(theme.palette, Palette::default(), "{}", name);
+        }
+
+        assert!(Theme::named("dark").unwrap().is_dark());
+        assert!(!Theme::named("light").unwrap().is_dark());
+        assert!(Theme::named("nope").is_none());
+    }
+
+    #[test]
+    fn test_invert() {
+        let theme = ThemeBuilder::new()
+            .color(PaletteColor::Primary, Color::Rgb(10, 20, 30))
+            .color(PaletteColor::Secondary, Color::RgbLowRes(1, 2, 3))
+            .color(PaletteColor::Tertiary, Color::TerminalDefault)
+            .build();
+
+        let inverted = theme.invert();
+        assert_eq!(
+            inverted.palette[PaletteColor::Primary],
+            Color::Rgb(245, 235, 225)
+        );
+        assert_eq!(
+            inverted.palette[PaletteColor::Secondary],
+            Color::RgbLowRes(4, 3, 2)
+        );
+        assert_eq!(
+            inverted.palette[PaletteColor::Background],
+            Color::Light(BaseColor::Yellow)
+        );
+        assert_ne!(theme.is_dark(), inverted.is_dark());
+
+        // Inverting twice gives back about the same colors.
+        let twice = inverted.invert();
+        for ((role, before), (_, after)) in
+            theme.palette.iter().zip(twice.palette.iter())
+        {
+            assert!(before.distance(after) < 100.0, "{}", role);
+        }
+        assert_eq!(
+            twice.palette[PaletteColor::Primary],
+            Color::Rgb(10, 20, 30)
+        );
+    }
+
+    #[test]
+    fn test_preview() {
+        let preview = Theme::default().preview();
+        assert_eq!(preview.lines().count(), ColorStyle::all().len());
+
+        for name in &[
+            "terminal_default",
+            "background",
+            "shadow",
+            "primary",
+            "secondary",
+            "tertiary",
+            "title_primary",
+            "title_secondary",
+            "title_focused",
+            "highlight",
+            "highlight_inactive",
+            "row_even",
+            "row_odd",
+            "error",
+            "warning",
+            "success",
+        ] {
+            assert!(preview.contains(&format!("{}: ", name)), "{}", name);
+        }
+
+        assert!(preview.contains("terminal_default: default on default\n"));
+        assert!(preview.contains("primary: #000000 on #aaaaaa\n"));
+    }
+
+    #[test]
+    fn test_from_accent() {
+        for &accent in &[
+            Color::Rgb(0x26, 0x8b, 0xd2),
+            Color::Rgb(0xff, 0xd7, 0x00),
+            Color::Dark(BaseColor::Red),
+            Color::Light(BaseColor::Green),
+        ] {
+            let theme = Theme::from_accent(accent);
+            let palette = &theme.palette;
+
+            assert_eq!(palette[PaletteColor::Highlight], accent);
+            assert!(
+                palette[PaletteColor::Primary]
+                    .contrast_ratio(palette[PaletteColor::View])
+                    >= 4.5
+            );
+        }
+    }
+
+    #[test]
+    fn test_downgrade() {
+        let theme = Theme::from_accent(Color::Rgb(0x26, 0x8b, 0xd2));
+
+        let downgraded = theme.downgrade(ColorDepth::Palette256);
+        assert_eq!(
+            downgraded.palette[PaletteColor::Highlight],
+            Color::RgbLowRes(1, 3, 4)
+        );
+
+        let downgraded = theme.downgrade(ColorDepth::Basic8);
+        for (_, color) in downgraded.palette.iter() {
+            assert!(matches!(color, Color::Dark(_)));
+        }
+
+        assert_eq!(
+            theme.downgrade(ColorDepth::TrueColor).palette,
+            theme.palette
+        );
+    }
+
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    /// Serializes `theme` and parses it back, checking every field survived.
+    fn assert_roundtrip<S, P, E>(theme: &Theme, serialize: S, parse: P)
+    where
+        S: Fn(&Theme) -> String,
+        P: Fn(&str) -> Result<Theme, E>,
+        E: std::fmt::Debug,
+    {
+        let content = serialize(theme);
+        let loaded = parse(&content).unwrap_or_else(|e| {
+            panic!("Could not parse theme: {:?}\n{}", e, content)
+        });
+
+        assert_eq!(loaded.shadow, theme.shadow, "shadow diverged");
+        assert_eq!(loaded.borders, theme.borders, "borders diverged");
+        assert_eq!(loaded.effects, theme.effects, "effects diverged");
+        for ((role, expected), (_, actual)) in
+            theme.palette.iter().zip(loaded.palette.iter())
+        {
+            assert_eq!(
+                actual, expected,
+                "palette role `{}` diverged\n{}",
+                role, content
+            );
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    fn theme_to_toml(theme: &Theme) -> String {
+        let mut content = format!(
+            "shadow = {}\nborders = \"{}\"\n\n[colors]\n",
+            theme.shadow, theme.borders
+        );
+        for (role, color) in theme.palette.iter() {
+            content += &format!("{} = \"{}\"\n", role, color);
+        }
+        content += "\n[effects]\n";
+        for (role, effects) in &theme.effects {
+            let effects: Vec<_> = effects
+                .iter()
+                .map(|effect| format!("\"{}\"", effect.as_str()))
+                .collect();
+            content +=
+                &format!("{} = [{}]\n", role.as_str(), effects.join(", "));
+        }
+        content
+    }
+
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    /// A theme using every kind of color.
+    fn sample_theme() -> Theme {
+        use self::BaseColor::*;
+        use self::Color::*;
+        use self::PaletteColor::*;
+
+        let mut theme = Theme {
+            shadow: false,
+            borders: BorderStyle::Outset,
+            ..Theme::default()
+        };
+        theme.palette.extend(vec![
+            (Background, TerminalDefault),
+            (Shadow, Dark(Black)),
+            (View, Light(White)),
+            (Primary, Rgb(0x12, 0x34, 0x56)),
+            (Secondary, RgbLowRes(1, 2, 3)),
+            (Tertiary, Light(Magenta)),
+            (TitlePrimary, Rgb(255, 0, 0)),
+            (TitleSecondary, RgbLowRes(5, 5, 0)),
+        ]);
+        theme.effects[TitlePrimary] = Effect::Bold | Effect::Underline;
+        theme.effects[Highlight] = EnumSet::only(Effect::Reverse);
+        theme
+    }
+
+    /// A random theme, for property tests.
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    fn random_theme<R: rand::Rng>(rng: &mut R) -> Theme {
+        let mut random_color = || {
+            let base = BaseColor::all()[rng.gen_range(0, 8)];
+            match rng.gen_range(0, 5) {
+                0 => Color::TerminalDefault,
+                1 => Color::Dark(base),
+                2 => Color::Light(base),
+                3 => Color::Rgb(rng.gen(), rng.gen(), rng.gen()),
+                _ => Color::RgbLowRes(
+                    rng.gen_range(0, 6),
+                    rng.gen_range(0, 6),
+                    rng.gen_range(0, 6),
+                ),
+            }
+        };
+        let mut palette = Palette::default();
+        for (role, _) in Palette::default().iter() {
+            palette.set(role, random_color());
+        }
+
+        let mut theme = Theme {
+            shadow: rng.gen(),
+            borders: [
+                BorderStyle::Simple,
+                BorderStyle::Outset,
+                BorderStyle::None,
+                BorderStyle::Double,
+                BorderStyle::Rounded,
+            ][rng.gen_range(0, 5)],
+            palette,
+            ..Theme::default()
+        };
+        for (_, effects) in &mut theme.effects {
+            *effects = EnumSet::<Effect>::all()
+                .iter()
+                .filter(|_| rng.gen_bool(0.3))
+                .collect();
+        }
+        theme
+    }
+
+    /// Runs `assert_roundtrip` on many random themes.
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    fn assert_random_roundtrips<S, P, E>(serialize: S, parse: P)
+    where
+        S: Fn(&Theme) -> String,
+        P: Fn(&str) -> Result<Theme, E>,
+        E: std::fmt::Debug,
+    {
+        use rand::SeedableRng;
+
+        // A fixed seed keeps failures reproducible.
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x7e3e);
+        for _ in 0..200 {
+            assert_roundtrip(&random_theme(&mut rng), &serialize, &parse);
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_roundtrip() {
+        assert_roundtrip(&Theme::default(), theme_to_toml, load_toml);
+        assert_roundtrip(&sample_theme(), theme_to_toml, load_toml);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_random_roundtrip() {
+        assert_random_roundtrips(theme_to_toml, load_toml);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_random_roundtrip() {
+        assert_random_roundtrips(
+            |theme: &Theme| serde_json::to_string(theme).unwrap(),
+            |content: &str| serde_json::from_str::<Theme>(content),
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_roundtrip() {
+        let serialize = |theme: &Theme| serde_json::to_string(theme).unwrap();
+        let parse = |content: &str| serde_json::from_str::<Theme>(content);
+
+        assert_roundtrip(&Theme::default(), serialize, parse);
+        assert_roundtrip(&sample_theme(), serialize, parse);
+
+        // Missing fields keep their default value.
+        let theme = parse(r#"{"borders": "none"}"#).unwrap();
+        assert_eq!(theme.borders, BorderStyle::None);
+        assert_eq!(theme.palette, Palette::default());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_invalid_borders() {
+        let theme = load_toml(r#"borders = "simpel""#).unwrap();
+        assert_eq!(theme.borders, Theme::default().borders);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_warnings() {
+        let (theme, warnings) = load_toml_with_warnings(
+            r##"
+            [colors]
+            primary = "rd"
+            secondary = ["nope", "nada"]
+            view = "#000000"
+            tertiary = "111"
+            title_primary = "555"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings.iter().any(|w| w.contains("`primary`")));
+        assert!(warnings.iter().any(|w| w.contains("Did you mean `red`?")));
+        assert!(warnings.iter().any(|w| w.contains("`secondary`")));
+        assert!(warnings.iter().any(|w| w.contains("Use `lowres(1,1,1)`")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("Did you mean `lowres(5,5,5)`?")));
+        assert_eq!(
+            theme.palette[PaletteColor::Tertiary],
+            Color::from_256colors(111)
+        );
+        assert_eq!(
+            theme.palette[PaletteColor::Primary],
+            Theme::default().palette[PaletteColor::Primary]
+        );
+        assert_eq!(theme.palette[PaletteColor::View], Color::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_error_display() {
+        use std::error::Error as _;
+
+        let err = Error::Io(io::Error::new(io::ErrorKind::NotFound, "nope"));
+        assert_eq!(err.to_string(), "could not read theme: nope");
+        assert_eq!(err.source().unwrap().to_string(), "nope");
+
+        #[cfg(feature = "toml")]
+        {
+            let err = load_toml("shadow = ").unwrap_err();
+            assert!(err.to_string().starts_with("could not parse theme: "));
+            assert!(err.source().is_some());
+        }
+
+        #[cfg(feature = "serde_json")]
+        {
+            let err = Theme::from_iterm_scheme("[").unwrap_err();
+            assert!(err
+                .to_string()
+                .starts_with("could not parse color scheme: "));
+            assert!(err.source().is_some());
+        }
+
+        // Theme errors can be used with `?` in functions returning a boxed
+        // error.
+        let boxed: Box<dyn std::error::Error> = Box::new(err);
+        assert!(boxed.source().is_some());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_env() {
+        use std::env;
+
+        let var = "CURSIVE_TEST_THEME_FROM_ENV";
+        env::remove_var(var);
+        let theme = Theme::from_env(var).unwrap();
+        assert_eq!(theme.palette, Theme::default().palette);
+
+        let path = env::temp_dir().join(format!(
+            "cursive-theme-from-env-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "borders = \"none\"").unwrap();
+        env::set_var(var, &path);
+        let theme = Theme::from_env(var).unwrap();
+        assert_eq!(theme.borders, BorderStyle::None);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(Theme::from_env(var), Err(Error::Io(_))));
+        env::remove_var(var);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_theme_files() {
+        let dir = std::env::temp_dir();
+        let path = |name: &str| {
+            dir.join(format!(
+                "cursive-theme-files-{}-{}.toml",
+                name,
+                std::process::id()
+            ))
+        };
+        let (base, user, missing) =
+            (path("base"), path("user"), path("missing"));
+
+        std::fs::write(
+            &base,
+            r#"
+            shadow = false
+            [colors]
+            primary = "red"
+            view = "blue"
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            &user,
+            r#"
+            [colors]
+            primary = "green"
+            "#,
+        )
+        .unwrap();
+
+        let theme = load_theme_files(&[&base, &missing, &user], true).unwrap();
+        assert!(!theme.shadow);
+        assert_eq!(
+            theme.palette[PaletteColor::Primary],
+            Color::Dark(BaseColor::Green)
+        );
+        assert_eq!(
+            theme.palette[PaletteColor::View],
+            Color::Dark(BaseColor::Blue)
+        );
+
+        assert!(matches!(
+            load_theme_files(&[&base, &missing, &user], false),
+            Err(Error::Io(_))
+        ));
+
+        std::fs::remove_file(&base).unwrap();
+        std::fs::remove_file(&user).unwrap();
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_toml_strict() {
+        let content = r#"
+            shadow = false
+            [colors]
+            primary = "red"
+            [colors.my_app]
+            accent = "blue"
+            "#;
+        let theme = load_toml_strict(content).unwrap();
+        assert!(!theme.shadow);
+        assert_eq!(
+            theme.palette[PaletteColor::Primary],
+            Color::Dark(BaseColor::Red)
+        );
+
+        let errors = load_toml_strict(
+            r#"
+            shadows = false
+            [colors]
+            primray = "red"
+            view = "not a color"
+            "#,
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&"Unknown key `shadows`.".to_string()));
+        assert!(errors.contains(&"Unknown color `primray`.".to_string()));
+
+        // The lenient loader ignores these.
+        assert!(load_toml("[colors]\nprimray = \"red\"").is_ok());
+        assert!(load_toml_strict("shadow = ").is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_version() {
+        let theme = load_toml("version = 1\nshadow = false").unwrap();
+        assert_eq!(theme.version, 1);
+        assert!(!theme.shadow);
+
+        let theme = load_toml("shadow = false").unwrap();
+        assert_eq!(theme.version, 1);
+
+        for content in &["version = 2", "version = 0", "version = \"1\""] {
+            let err = load_toml(content).unwrap_err();
+            assert!(
+                matches!(err, Error::UnsupportedVersion(_)),
+                "{}",
+                content
+            );
+        }
+        assert_eq!(
+            load_toml("version = 99").unwrap_err().to_string(),
+            "unsupported theme version: 99 (latest supported: 1)"
+        );
+        assert!(load_toml_strict("version = 99").is_err());
+    }
+
+    #[test]
+    fn test_row_odd_default() {
+        let palette = Theme::default().palette;
+        assert_eq!(
+            palette[PaletteColor::RowEven],
+            palette[PaletteColor::View]
+        );
+        // Light views get darker odd rows.
+        assert!(
+            palette[PaletteColor::RowOdd].luminance()
+                < palette[PaletteColor::View].luminance()
+        );
+
+        // Dark views get lighter odd rows.
+        let palette = Theme::named("dark").unwrap().palette;
+        assert!(
+            palette[PaletteColor::RowOdd].luminance()
+                > palette[PaletteColor::View].luminance()
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_rows() {
+        let theme = load_toml(
+            r##"
+            [colors]
+            view = "#ffffff"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            theme.palette[PaletteColor::RowEven],
+            Color::Rgb(255, 255, 255)
+        );
+        assert_eq!(
+            theme.palette[PaletteColor::RowOdd],
+            Color::Rgb(235, 235, 235)
+        );
+
+        let theme = load_toml(
+            r##"
+            [colors]
+            view = "#ffffff"
+            row_even = "light white"
+            row_odd = "#eeeeee"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            ColorStyle::row_even().resolve(&theme.palette).back,
+            Color::Light(BaseColor::White)
+        );
+        assert_eq!(
+            ColorStyle::row_odd().resolve(&theme.palette).back,
+            Color::Rgb(0xee, 0xee, 0xee)
+        );
+    }
+
+    #[test]
+    fn test_status_defaults() {
+        let theme = Theme::default();
+        assert_eq!(
+            ColorStyle::error().resolve(&theme.palette),
+            ColorPair {
+                front: Color::Dark(BaseColor::Red),
+                back: theme.palette[PaletteColor::View],
+            }
+        );
+        assert_eq!(
+            theme.palette[PaletteColor::Warning],
+            Color::Dark(BaseColor::Yellow)
+        );
+        assert_eq!(
+            theme.palette[PaletteColor::Success],
+            Color::Dark(BaseColor::Green)
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_status() {
+        let theme = load_toml(
+            r##"
+            [colors]
+            error = "light red"
+            warning = "#ffaa00"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            ColorStyle::error().resolve(&theme.palette).front,
+            Color::Light(BaseColor::Red)
+        );
+        assert_eq!(
+            ColorStyle::warning().resolve(&theme.palette).front,
+            Color::Rgb(0xff, 0xaa, 0x00)
+        );
+        assert_eq!(
+            ColorStyle::success().resolve(&theme.palette).front,
+            Color::Dark(BaseColor::Green)
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_title_focused() {
+        let theme = load_toml(
+            r#"
+            [colors]
+            title_primary = "green"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            theme.palette[PaletteColor::TitleFocused],
+            Color::Dark(BaseColor::Green)
+        );
+
+        let theme = load_toml(
+            r#"
+            [colors]
+            title_primary = "green"
+            title_focused = "light cyan"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            theme.palette[PaletteColor::TitleFocused],
+            Color::Light(BaseColor::Cyan)
+        );
+        assert_eq!(
+            ColorStyle::title_focused().resolve(&theme.palette).front,
+            Color::Light(BaseColor::Cyan)
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_not_a_table() {
+        for content in &["[1, 2, 3]", "shadow = ", "= 4"] {
+            match load_toml(content) {
+                Err(Error::Parse(err)) => assert!(!err.to_string().is_empty()),
+                other => panic!("Expected a parse error, got {:?}", other),
+            }
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_effects() {
+        let (theme, warnings) = load_toml_with_warnings(
+            r#"
+            [effects]
+            title_primary = "bold"
+            highlight = ["reverse", "italic"]
+            primary = "blink"
+            header = "bold"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            theme.effects[PaletteColor::TitlePrimary],
+            EnumSet::only(Effect::Bold)
+        );
+        assert_eq!(
+            theme.effects[PaletteColor::Highlight],
+            Effect::Reverse | Effect::Italic
+        );
+        assert!(theme.effects[PaletteColor::Primary].is_empty());
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_toml_onto() {
+        let mut base = Theme::default();
+        base.palette[PaletteColor::View] = Color::Rgb(1, 2, 3);
+
+        let theme = load_toml_onto(
+            r##"
+            [colors]
+            primary = "#ffffff"
+            "##,
+            base,
+        )
+        .unwrap();
+
+        assert_eq!(theme.palette[PaletteColor::View], Color::Rgb(1, 2, 3));
+        assert_eq!(
+            theme.palette[PaletteColor::Primary],
+            Color::Rgb(255, 255, 255)
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_toml_onto_keeps_explicit_fallback_roles() {
+        let base = load_toml(
+            r##"
+            [colors]
+            title_primary = "red"
+            title_focused = "#00ff00"
+            "##,
+        )
+        .unwrap();
+
+        let theme = load_toml_onto(
+            r##"
+            [colors]
+            title_primary = "blue"
+            view = "#ffffff"
+            "##,
+            base,
+        )
+        .unwrap();
+
+        let palette = &theme.palette;
+        assert_eq!(
+            palette[PaletteColor::TitlePrimary],
+            Color::Dark(BaseColor::Blue)
+        );
+        // Set explicitly by the base theme.
+        assert_eq!(palette[PaletteColor::TitleFocused], Color::Rgb(0, 255, 0));
+        // Never set, so they still follow `View`.
+        assert_eq!(palette[PaletteColor::RowEven], Color::Rgb(255, 255, 255));
+        assert_ne!(palette[PaletteColor::RowOdd], Color::Rgb(255, 255, 255));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_toml_with_depth() {
+        let content = r##"
+            [colors]
+            primary = { truecolor = "#123456", palette256 = "lowres(0,1,2)", basic = "blue" }
+            secondary = { basic8 = ["nope", "red"] }
+            tertiary = { truecolor = "#ffffff" }
+
+            [colors.menu]
+            view = "black"
+            "##;
+
+        let primary = |depth| {
+            load_toml_with_depth(content, depth).unwrap().palette
+                [PaletteColor::Primary]
+        };
+        assert_eq!(
+            primary(ColorDepth::TrueColor),
+            Color::Rgb(0x12, 0x34, 0x56)
+        );
+        assert_eq!(primary(ColorDepth::Palette256), Color::RgbLowRes(0, 1, 2));
+        assert_eq!(primary(ColorDepth::Basic16), Color::Dark(BaseColor::Blue));
+        assert_eq!(primary(ColorDepth::Basic8), Color::Dark(BaseColor::Blue));
+
+        let theme =
+            load_toml_with_depth(content, ColorDepth::Basic16).unwrap();
+        assert_eq!(
+            theme.palette[PaletteColor::Secondary],
+            Color::Dark(BaseColor::Red)
+        );
+        // No color is given for this depth: keep the default.
+        assert_eq!(
+            theme.palette[PaletteColor::Tertiary],
+            Theme::default().palette[PaletteColor::Tertiary]
+        );
+        // Other tables are still namespaces.
+        assert_eq!(
+            theme.palette.merge("menu")[PaletteColor::View],
+            Color::Dark(BaseColor::Black)
+        );
+
+        // Other loaders use the best color available.
+        let theme = load_toml(content).unwrap();
+        assert_eq!(
+            theme.palette[PaletteColor::Primary],
+            Color::Rgb(0x12, 0x34, 0x56)
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use super::Color;
+#[cfg(feature = "toml")]
+use super::{color::legacy_low_res, ColorDepth};
 use enum_map::{enum_map, Enum, EnumMap};
 #[cfg(feature = "toml")]
 use log::warn;
 
+use std::hash::{Hash, Hasher};
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
@@ -130,6 +133,220 @@ impl Palette {
         PaletteColor::from_str(key).map(|c| self.basic[c] = color)
     }
 
+    /// Returns the color for the given role name.
+    ///
+    /// Returns `None` if `role` is not a known `PaletteColor`.
+    pub fn get(&self, role: &str) -> Option<Color> {
+        PaletteColor::from_str(role).ok().map(|role| self[role])
+    }
+
+    /// Sets the color for the given role name.
+    ///
+    /// Returns `false` if `role` is not a known `PaletteColor`.
+    pub fn set(&mut self, role: &str, color: Color) -> bool {
+        self.set_basic_color(role, color).is_ok()
+    }
+
+    /// Returns an iterator on every basic color role and its current color.
+    ///
+    /// Roles are named as in theme files (`"background"`, `"primary"`, ...)
+    /// and are always yielded in the same order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Color)> + '_ {
+        self.basic
+            .iter()
+            .map(|(role, &color)| (role.as_str(), color))
+    }
+
+    /// Returns a new palette with `f` applied to every color.
+    ///
+    /// This includes custom colors, in every namespace.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive_core::theme::Palette;
+    /// // A dimmed version of the default palette.
+    /// let dimmed = Palette::default().map_colors(|color| color.darken(0.1));
+    /// ```
+    pub fn map_colors<F>(&self, f: F) -> Palette
+    where
+        F: Fn(Color) -> Color,
+    {
+        Palette {
+            basic: EnumMap::from(|role| f(self.basic[role])),
+            custom: map_nodes(&self.custom, &f),
+        }
+    }
+
+    /// Returns a palette between this one and `other`.
+    ///
+    /// Each color is mixed with the matching color from `other` using
+    /// [`Color::mix`], with `t` as the weight of `other`: `0.0` returns this
+    /// palette, and `1.0` returns `other`. This can be used to fade between
+    /// two themes.
+    ///
+    /// Custom colors are mixed when they exist in both palettes. Other custom
+    /// values are taken from the closest palette.
+    ///
+    /// [`Color::mix`]: enum.Color.html#method.mix
+    pub fn blend_with(&self, other: &Palette, t: f32) -> Palette {
+        Palette {
+            basic: EnumMap::from(|role| {
+                self.basic[role].mix(other.basic[role], t)
+            }),
+            custom: blend_nodes(&self.custom, &other.custom, t),
+        }
+    }
+
+    /// Returns the contrast ratio of each text role against its background.
+    ///
+    /// Text roles (`primary`, `secondary`, `tertiary`, titles, `error`,
+    /// `warning` and `success`) are checked against `view`, and
+    /// `highlight_text` is checked against both highlight backgrounds. Each
+    /// entry is labeled like `"primary on view"`.
+    ///
+    /// See [`Color::contrast_ratio`].
+    ///
+    /// [`Color::contrast_ratio`]: enum.Color.html#method.contrast_ratio
+    pub fn contrast_report(&self) -> Vec<(String, f32)> {
+        TEXT_ROLES
+            .iter()
+            .map(|&(front, back)| {
+                (
+                    format!("{} on {}", front.as_str(), back.as_str()),
+                    self[front].contrast_ratio(self[back]),
+                )
+            })
+            .collect()
+    }
+
+    /// Changes text colors until they are readable on `view`.
+    ///
+    /// Each text role from [`contrast_report`] printed on `view` with a
+    /// contrast ratio below `min_ratio` is gradually lightened or darkened,
+    /// whichever contrasts the most with `view`, until it reaches
+    /// `min_ratio`. If the ratio cannot be reached, the role ends up white or
+    /// black.
+    ///
+    /// [`contrast_report`]: #method.contrast_report
+    pub fn ensure_contrast(&mut self, min_ratio: f32) {
+        let view = self[PaletteColor::View];
+        let white = Color::Rgb(255, 255, 255);
+        let black = Color::Rgb(0, 0, 0);
+        let lighten = white.contrast_ratio(view) > black.contrast_ratio(view);
+
+        for &(role, back) in &TEXT_ROLES {
+            if back != PaletteColor::View {
+                continue;
+            }
+
+            let color = self[role];
+            if color.contrast_ratio(view) >= min_ratio {
+                continue;
+            }
+
+            self[role] = (1..=20)
+                .map(|step| {
+                    let amount = step as f32 / 20.0;
+                    if lighten {
+                        color.lighten(amount)
+                    } else {
+                        color.darken(amount)
+                    }
+                })
+                .find(|candidate| candidate.contrast_ratio(view) >= min_ratio)
+                .unwrap_or(if lighten { white } else { black });
+        }
+    }
+
+    /// Returns the roles whose color currently comes from their fallback.
+    ///
+    /// These are the roles that were not given a color of their own, and
+    /// that should follow their fallback role when it changes.
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    fn following_fallbacks(&self) -> Vec<PaletteColor> {
+        self.basic
+            .iter()
+            .filter(|&(role, &color)| self.fallback_color(role) == Some(color))
+            .map(|(role, _)| role)
+            .collect()
+    }
+
+    /// Returns the color `role` gets from its fallback role, if any.
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    fn fallback_color(&self, role: PaletteColor) -> Option<Color> {
+        Some(role.inherit(self.basic[role.fallback()?]))
+    }
+
+    /// Gives roles missing from `keys` the color of their fallback role.
+    ///
+    /// `keys` are the roles that were just loaded, and `following` the
+    /// roles that followed their fallback before loading (see
+    /// `following_fallbacks`). Only roles from `following` whose fallback
+    /// was loaded are changed: a role with its own color, for example from
+    /// a base theme, is kept.
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    fn fill_fallbacks<'a, I>(&mut self, keys: I, following: &[PaletteColor])
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let loaded: Vec<PaletteColor> = keys
+            .into_iter()
+            .filter_map(|key| key.parse().ok())
+            .collect();
+
+        for &role in following {
+            if let Some(fallback) = role.fallback() {
+                if !loaded.contains(&role) && loaded.contains(&fallback) {
+                    if let Some(color) = self.fallback_color(role) {
+                        self.basic[role] = color;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sets both row colors from the `View` color.
+    ///
+    /// `RowEven` uses the `View` color, and `RowOdd` a slightly shaded
+    /// version of it.
+    pub(crate) fn update_rows(&mut self) {
+        let view = self.basic[PaletteColor::View];
+        self.basic[PaletteColor::RowEven] = view;
+        self.basic[PaletteColor::RowOdd] = shade_row(view);
+    }
+
+    /// Sets a color or adds a namespace, depending on `node`.
+    /// Returns the custom keys with a different value in `other`.
+    ///
+    /// Each key comes with its value in both palettes, and keys are sorted.
+    pub(crate) fn custom_changes<'a>(
+        &'a self,
+        other: &'a Palette,
+    ) -> Vec<(&'a str, Option<&'a PaletteNode>, Option<&'a PaletteNode>)> {
+        let mut keys: Vec<&str> = self
+            .custom
+            .keys()
+            .chain(other.custom.keys())
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        keys.into_iter()
+            .map(|key| (key, self.custom.get(key), other.custom.get(key)))
+            .filter(|(_, old, new)| old != new)
+            .collect()
+    }
+
+    #[cfg(any(feature = "toml", feature = "serde"))]
+    fn set_node(&mut self, key: &str, node: PaletteNode) {
+        match node {
+            PaletteNode::Color(color) => self.set_color(key, color),
+            PaletteNode::Namespace(map) => self.add_namespace(key, map),
+        }
+    }
+
     /// Adds a color namespace to this palette.
     pub fn add_namespace(
         &mut self,
@@ -141,6 +358,94 @@ impl Palette {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Palette {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        for (role, color) in self.iter() {
+            map.serialize_entry(role, &color)?;
+        }
+        for (key, node) in &self.custom {
+            map.serialize_entry(key, node)?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes a palette the same way theme files are loaded.
+///
+/// Missing roles keep their default color.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Palette {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let nodes = HashMap::<String, PaletteNode>::deserialize(deserializer)?;
+
+        let mut palette = Palette::default();
+        let following = palette.following_fallbacks();
+        for (key, node) in &nodes {
+            palette.set_node(key, node.clone());
+        }
+        palette.fill_fallbacks(nodes.keys().map(String::as_str), &following);
+        Ok(palette)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PaletteNode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match *self {
+            PaletteNode::Color(ref color) => color.serialize(serializer),
+            PaletteNode::Namespace(ref map) => map.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PaletteNode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        // Same shapes as in theme files.
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum RawNode {
+            Color(String),
+            Colors(Vec<String>),
+            Namespace(HashMap<String, PaletteNode>),
+        }
+
+        match RawNode::deserialize(deserializer)? {
+            RawNode::Color(color) => {
+                Color::parse(&color).map(PaletteNode::Color).ok_or_else(|| {
+                    D::Error::custom(format!("invalid color `{}`", color))
+                })
+            }
+            RawNode::Colors(colors) => {
+                let candidates: Vec<&str> =
+                    colors.iter().map(String::as_str).collect();
+                Color::first_valid(&candidates)
+                    .map(PaletteNode::Color)
+                    .ok_or_else(|| D::Error::custom("no valid color in list"))
+            }
+            RawNode::Namespace(map) => Ok(PaletteNode::Namespace(map)),
+        }
+    }
+}
+
 impl Extend<(PaletteColor, Color)> for Palette {
     fn extend<T>(&mut self, iter: T)
     where
@@ -152,6 +457,78 @@ impl Extend<(PaletteColor, Color)> for Palette {
     }
 }
 
+// Custom colors are hashed in key order, since `HashMap` has no set order.
+impl Hash for Palette {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.basic.hash(state);
+        hash_nodes(&self.custom, state);
+    }
+}
+
+impl Hash for PaletteNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            PaletteNode::Color(color) => {
+                state.write_u8(0);
+                color.hash(state);
+            }
+            PaletteNode::Namespace(nodes) => {
+                state.write_u8(1);
+                hash_nodes(nodes, state);
+            }
+        }
+    }
+}
+
+fn hash_nodes<H: Hasher>(nodes: &HashMap<String, PaletteNode>, state: &mut H) {
+    let mut keys: Vec<&String> = nodes.keys().collect();
+    keys.sort();
+
+    state.write_usize(keys.len());
+    for key in keys {
+        key.hash(state);
+        nodes[key].hash(state);
+    }
+}
+
+/// Iterates on the basic colors, in role order.
+///
+/// Use [`Palette::iter`] to also get the role names.
+///
+/// [`Palette::iter`]: struct.Palette.html#method.iter
+impl<'a> IntoIterator for &'a Palette {
+    type Item = Color;
+    type IntoIter = Colors<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Colors {
+            inner: self.basic.as_slice().iter(),
+        }
+    }
+}
+
+/// Iterator on the basic colors of a palette, in role order.
+///
+/// This is returned by iterating on a `&Palette`.
+#[derive(Clone, Debug)]
+pub struct Colors<'a> {
+    inner: std::slice::Iter<'a, Color>,
+}
+
+impl<'a> Iterator for Colors<'a> {
+    type Item = Color;
+
+    fn next(&mut self) -> Option<Color> {
+        self.inner.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Colors<'a> {}
+
 /// Returns the default palette for a cursive application.
 ///
 /// * `Background` => `Dark(Blue)`
@@ -161,10 +538,15 @@ impl Extend<(PaletteColor, Color)> for Palette {
 /// * `Secondary` => `Dark(Blue)`
 /// * `Tertiary` => `Light(White)`
 /// * `TitlePrimary` => `Dark(Red)`
-/// * `TitleSecondary` => `Dark(Yellow)`
+/// * `TitleSecondary` => `Light(Blue)`
 /// * `Highlight` => `Dark(Red)`
 /// * `HighlightInactive` => `Dark(Blue)`
 /// * `HighlightText` => `Dark(White)`
+/// * `RowEven` => `Dark(White)`
+/// * `RowOdd` => `Dark(White)`, slightly darkened
+/// * `Error` => `Dark(Red)`
+/// * `Warning` => `Dark(Yellow)`
+/// * `Success` => `Dark(Green)`
 impl Default for Palette {
     fn default() -> Palette {
         use self::PaletteColor::*;
@@ -181,9 +563,15 @@ impl Default for Palette {
                 Tertiary => Light(White),
                 TitlePrimary => Dark(Red),
                 TitleSecondary => Light(Blue),
+                TitleFocused => Dark(Red),
                 Highlight => Dark(Red),
                 HighlightInactive => Dark(Blue),
                 HighlightText => Dark(White),
+                RowEven => Dark(White),
+                RowOdd => shade_row(Dark(White)),
+                Error => Dark(Red),
+                Warning => Dark(Yellow),
+                Success => Dark(Green),
             },
             custom: HashMap::default(),
         }
@@ -191,17 +579,46 @@ impl Default for Palette {
 }
 
 // Iterate over a toml
+//
+// Values that could not be loaded are reported in `warnings`.
 #[cfg(feature = "toml")]
 fn iterate_toml<'a>(
     table: &'a toml::value::Table,
-) -> impl Iterator<Item = (&'a str, PaletteNode)> + 'a {
-    table.iter().flat_map(|(key, value)| {
+    depth: ColorDepth,
+    warnings: &mut Vec<String>,
+) -> Vec<(&'a str, PaletteNode)> {
+    let mut nodes = Vec::new();
+
+    for (key, value) in table {
         let node = match value {
+            toml::Value::Table(table) if is_depth_table(table) => {
+                // This gives a different color for each color depth.
+                let node = depth_keys(depth)
+                    .iter()
+                    .find_map(|&depth| table.get(depth))
+                    .and_then(|value| match value {
+                        toml::Value::String(color) => Color::parse(color),
+                        toml::Value::Array(colors) => {
+                            let candidates: Vec<&str> = colors
+                                .iter()
+                                .flat_map(toml::Value::as_str)
+                                .collect();
+                            Color::first_valid(&candidates)
+                        }
+                        _ => None,
+                    })
+                    .map(PaletteNode::Color);
+                if node.is_none() {
+                    warnings.push(format!("No valid color for `{}`.", key));
+                }
+                node
+            }
             toml::Value::Table(table) => {
                 // This should define a new namespace
                 // Treat basic colors as simple string.
                 // We'll convert them back in the merge method.
-                let map = iterate_toml(table)
+                let map = iterate_toml(table, depth, warnings)
+                    .into_iter()
                     .map(|(key, value)| (key.to_string(), value))
                     .collect();
                 // Should we only return something if it's non-empty?
@@ -209,16 +626,38 @@ fn iterate_toml<'a>(
             }
             toml::Value::Array(colors) => {
                 // This should be a list of colors - just pick the first valid one.
-                colors
-                    .iter()
-                    .flat_map(toml::Value::as_str)
-                    .flat_map(Color::parse)
-                    .map(PaletteNode::Color)
-                    .next()
+                let candidates: Vec<&str> =
+                    colors.iter().flat_map(toml::Value::as_str).collect();
+                let node =
+                    Color::first_valid(&candidates).map(PaletteNode::Color);
+                if node.is_none() {
+                    warnings.push(format!("No valid color for `{}`.", key));
+                }
+                node
             }
             toml::Value::String(color) => {
                 // This describe a new color - easy!
-                Color::parse(color).map(PaletteNode::Color)
+                let node = Color::parse(color).map(PaletteNode::Color);
+                let lowres = legacy_low_res(color);
+                if node.is_none() {
+                    let mut warning =
+                        format!("Invalid color for `{}`: `{}`.", key, color);
+                    let suggestion = lowres
+                        .or_else(|| Color::suggest(color).map(String::from));
+                    if let Some(suggestion) = suggestion {
+                        warning.push_str(&format!(
+                            " Did you mean `{}`?",
+                            suggestion
+                        ));
+                    }
+                    warnings.push(warning);
+                } else if let Some(lowres) = lowres {
+                    warnings.push(format!(
+                        "Color `{}` for `{}` is read as a 256-color index. Use `{}` for a low-resolution color.",
+                        color, key, lowres
+                    ));
+                }
+                node
             }
             other => {
                 // Other - error?
@@ -226,28 +665,164 @@ fn iterate_toml<'a>(
                     "Found unexpected value in theme: {} = {:?}",
                     key, other
                 );
+                warnings.push(format!("Unexpected value for `{}`.", key));
                 None
             }
         };
 
-        node.map(|node| (key.as_str(), node))
-    })
+        if let Some(node) = node {
+            nodes.push((key.as_str(), node));
+        }
+    }
+
+    nodes
 }
 
 /// Fills `palette` with the colors from the given `table`.
+///
+/// Returns a description of every value that could not be loaded.
 #[cfg(feature = "toml")]
-pub(crate) fn load_toml(palette: &mut Palette, table: &toml::value::Table) {
+pub(crate) fn load_toml(
+    palette: &mut Palette,
+    table: &toml::value::Table,
+    depth: ColorDepth,
+) -> Vec<String> {
     // TODO: use serde for that?
     // Problem: toml-rs doesn't do well with Enums...
 
-    for (key, value) in iterate_toml(table) {
-        match value {
-            PaletteNode::Color(color) => palette.set_color(key, color),
-            PaletteNode::Namespace(map) => palette.add_namespace(key, map),
-        }
+    let mut warnings = Vec::new();
+    let nodes = iterate_toml(table, depth, &mut warnings);
+    let keys: Vec<&str> = nodes.iter().map(|&(key, _)| key).collect();
+    let following = palette.following_fallbacks();
+    for (key, value) in nodes {
+        palette.set_node(key, value);
+    }
+    palette.fill_fallbacks(keys, &following);
+    warnings
+}
+
+/// Returns a warning for each key of the `[colors]` table that is not a
+/// known palette role.
+///
+/// Tables defining a namespace are accepted.
+#[cfg(feature = "toml")]
+pub(crate) fn unknown_keys(table: &toml::value::Table) -> Vec<String> {
+    table
+        .iter()
+        .filter(|&(key, value)| match value {
+            toml::Value::Table(table) if !is_depth_table(table) => false,
+            _ => PaletteColor::from_str(key).is_err(),
+        })
+        .map(|(key, _)| format!("Unknown color `{}`.", key))
+        .collect()
+}
+
+/// Keys used to give a color for each color depth, from the best one to the
+/// most compatible one.
+#[cfg(feature = "toml")]
+const DEPTH_KEYS: [&str; 5] =
+    ["truecolor", "palette256", "basic16", "basic8", "basic"];
+
+/// Returns the keys that can be used with the given depth, by preference.
+#[cfg(feature = "toml")]
+fn depth_keys(depth: ColorDepth) -> &'static [&'static str] {
+    match depth {
+        ColorDepth::TrueColor => &DEPTH_KEYS,
+        ColorDepth::Palette256 => &DEPTH_KEYS[1..],
+        ColorDepth::Basic16 => &DEPTH_KEYS[2..],
+        ColorDepth::Basic8 => &DEPTH_KEYS[3..],
+    }
+}
+
+/// Returns `true` if this table gives a color for each depth, rather than
+/// defining a namespace.
+#[cfg(feature = "toml")]
+fn is_depth_table(table: &toml::value::Table) -> bool {
+    !table.is_empty() && table.keys().all(|key| DEPTH_KEYS.contains(&&**key))
+}
+
+/// Mixes nodes from `a` with the matching nodes from `b`.
+///
+/// Keys are taken from the closest side.
+fn blend_nodes(
+    a: &HashMap<String, PaletteNode>,
+    b: &HashMap<String, PaletteNode>,
+    t: f32,
+) -> HashMap<String, PaletteNode> {
+    let closest = if t < 0.5 { a } else { b };
+
+    closest
+        .iter()
+        .map(|(key, node)| {
+            let (a_node, b_node) = if t < 0.5 {
+                (Some(node), b.get(key))
+            } else {
+                (a.get(key), Some(node))
+            };
+
+            let node = match (a_node, b_node) {
+                (Some(PaletteNode::Color(a)), Some(PaletteNode::Color(b))) => {
+                    PaletteNode::Color(a.mix(*b, t))
+                }
+                (
+                    Some(PaletteNode::Namespace(a)),
+                    Some(PaletteNode::Namespace(b)),
+                ) => PaletteNode::Namespace(blend_nodes(a, b, t)),
+                _ => node.clone(),
+            };
+            (key.clone(), node)
+        })
+        .collect()
+}
+
+/// Applies `f` to every color in the given nodes.
+fn map_nodes<F>(
+    nodes: &HashMap<String, PaletteNode>,
+    f: &F,
+) -> HashMap<String, PaletteNode>
+where
+    F: Fn(Color) -> Color,
+{
+    nodes
+        .iter()
+        .map(|(key, node)| {
+            let node = match node {
+                PaletteNode::Color(color) => PaletteNode::Color(f(*color)),
+                PaletteNode::Namespace(map) => {
+                    PaletteNode::Namespace(map_nodes(map, f))
+                }
+            };
+            (key.clone(), node)
+        })
+        .collect()
+}
+
+/// Returns a slightly shaded version of the `view` color, for odd rows.
+///
+/// Light colors are darkened, and dark colors are lightened.
+pub(crate) fn shade_row(view: Color) -> Color {
+    if view.luminance() < 0.179 {
+        view.lighten(0.08)
+    } else {
+        view.darken(0.08)
     }
 }
 
+/// Text roles, with the background they are usually printed on.
+pub(crate) const TEXT_ROLES: [(PaletteColor, PaletteColor); 11] = [
+    (PaletteColor::Primary, PaletteColor::View),
+    (PaletteColor::Secondary, PaletteColor::View),
+    (PaletteColor::Tertiary, PaletteColor::View),
+    (PaletteColor::TitlePrimary, PaletteColor::View),
+    (PaletteColor::TitleSecondary, PaletteColor::View),
+    (PaletteColor::TitleFocused, PaletteColor::View),
+    (PaletteColor::Error, PaletteColor::View),
+    (PaletteColor::Warning, PaletteColor::View),
+    (PaletteColor::Success, PaletteColor::View),
+    (PaletteColor::HighlightText, PaletteColor::Highlight),
+    (PaletteColor::HighlightText, PaletteColor::HighlightInactive),
+];
+
 /// Color entry in a palette.
 ///
 /// Each `PaletteColor` is used for a specific role in a default application.
@@ -269,19 +844,163 @@ pub enum PaletteColor {
     TitlePrimary,
     /// Secondary color used for title text.
     TitleSecondary,
+    /// Color used for the title of focused views.
+    ///
+    /// When loading a theme that doesn't set it, it uses the same color as
+    /// `TitlePrimary` (see [`PaletteColor::fallback`]).
+    ///
+    /// [`PaletteColor::fallback`]: #method.fallback
+    TitleFocused,
     /// Color used for highlighting text.
     Highlight,
     /// Color used for highlighting inactive text.
     HighlightInactive,
     /// Color used for highlighted text
     HighlightText,
+    /// Background color for even rows in lists and tables.
+    ///
+    /// When loading a theme that doesn't set it, it uses the same color as
+    /// `View`.
+    RowEven,
+    /// Background color for odd rows in lists and tables.
+    ///
+    /// When loading a theme that doesn't set it, it uses a slightly shaded
+    /// version of the `View` color.
+    RowOdd,
+    /// Color used for error messages.
+    Error,
+    /// Color used for warnings.
+    Warning,
+    /// Color used for success messages.
+    Success,
 }
 
 impl PaletteColor {
+    /// Returns every palette role, in declaration order.
+    pub fn all() -> [PaletteColor; 17] {
+        use PaletteColor::*;
+
+        [
+            Background,
+            Shadow,
+            View,
+            Primary,
+            Secondary,
+            Tertiary,
+            TitlePrimary,
+            TitleSecondary,
+            TitleFocused,
+            Highlight,
+            HighlightInactive,
+            HighlightText,
+            RowEven,
+            RowOdd,
+            Error,
+            Warning,
+            Success,
+        ]
+    }
+
     /// Given a palette, resolve `self` to a concrete color.
     pub fn resolve(self, palette: &Palette) -> Color {
         palette[self]
     }
+
+    /// Given a palette, resolve `self` to a concrete color, inheriting from
+    /// other roles when it is `Color::TerminalDefault`.
+    ///
+    /// The roles from [`fallback_chain`] are tried in order, and the first
+    /// color that is not `Color::TerminalDefault` is used. Like when loading
+    /// a theme, `RowOdd` uses a shaded version of that color. If every role
+    /// in the chain is also `Color::TerminalDefault`, so is the result.
+    ///
+    /// [`fallback_chain`]: #method.fallback_chain
+    pub fn resolve_with_fallbacks(self, palette: &Palette) -> Color {
+        if palette[self] != Color::TerminalDefault {
+            return palette[self];
+        }
+
+        self.fallback_chain()
+            .iter()
+            .map(|&role| palette[role])
+            .find(|&color| color != Color::TerminalDefault)
+            .map_or(Color::TerminalDefault, |color| self.inherit(color))
+    }
+
+    /// Returns the color `self` gets when inheriting `color` from one of
+    /// its fallback roles.
+    fn inherit(self, color: Color) -> Color {
+        match self {
+            PaletteColor::RowOdd => shade_row(color),
+            _ => color,
+        }
+    }
+
+    /// Returns the roles `self` inherits from when resolved with
+    /// [`resolve_with_fallbacks`], in the order they are tried.
+    ///
+    /// * `Secondary`, `TitlePrimary`, `Error`, `Warning` and `Success`
+    ///   inherit from `Primary`.
+    /// * `Tertiary` inherits from `Secondary`, then `Primary`.
+    /// * `TitleSecondary` and `TitleFocused` inherit from `TitlePrimary`,
+    ///   then `Primary`.
+    /// * `HighlightInactive` inherits from `Highlight`.
+    /// * `RowEven` and `RowOdd` inherit from `View`.
+    ///
+    /// Other roles don't inherit from anything.
+    ///
+    /// [`resolve_with_fallbacks`]: #method.resolve_with_fallbacks
+    pub fn fallback_chain(self) -> &'static [PaletteColor] {
+        use PaletteColor::*;
+
+        match self {
+            Secondary | TitlePrimary | Error | Warning | Success => &[Primary],
+            Tertiary => &[Secondary, Primary],
+            TitleSecondary | TitleFocused => &[TitlePrimary, Primary],
+            HighlightInactive => &[Highlight],
+            RowEven | RowOdd => &[View],
+            Background | Shadow | View | Primary | Highlight
+            | HighlightText => &[],
+        }
+    }
+
+    /// Returns the role used by default for `self`, if any.
+    ///
+    /// This is the first role from [`fallback_chain`]. When loading a
+    /// theme, roles that are not set and that still use the color of their
+    /// fallback role follow it. For example, `TitleFocused` falls back to
+    /// `TitlePrimary`, and both `RowEven` and `RowOdd` fall back to `View`
+    /// (shaded for `RowOdd`).
+    ///
+    /// [`fallback_chain`]: #method.fallback_chain
+    pub fn fallback(self) -> Option<PaletteColor> {
+        self.fallback_chain().first().copied()
+    }
+
+    /// Returns the name of this role, as used in theme files.
+    pub fn as_str(self) -> &'static str {
+        use PaletteColor::*;
+
+        match self {
+            Background => "background",
+            Shadow => "shadow",
+            View => "view",
+            Primary => "primary",
+            Secondary => "secondary",
+            Tertiary => "tertiary",
+            TitlePrimary => "title_primary",
+            TitleSecondary => "title_secondary",
+            TitleFocused => "title_focused",
+            Highlight => "highlight",
+            HighlightInactive => "highlight_inactive",
+            HighlightText => "highlight_text",
+            RowEven => "row_even",
+            RowOdd => "row_odd",
+            Error => "error",
+            Warning => "warning",
+            Success => "success",
+        }
+    }
 }
 
 impl FromStr for PaletteColor {
@@ -299,10 +1018,293 @@ impl FromStr for PaletteColor {
             "Tertiary" | "tertiary" => Tertiary,
             "TitlePrimary" | "title_primary" => TitlePrimary,
             "TitleSecondary" | "title_secondary" => TitleSecondary,
+            "TitleFocused" | "title_focused" => TitleFocused,
             "Highlight" | "highlight" => Highlight,
             "HighlightInactive" | "highlight_inactive" => HighlightInactive,
             "HighlightText" | "highlight_text" => HighlightText,
+            "RowEven" | "row_even" => RowEven,
+            "RowOdd" | "row_odd" => RowOdd,
+            "Error" | "error" => Error,
+            "Warning" | "warning" => Warning,
+            "Success" | "success" => Success,
             _ => return Err(()),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "serde")]
+    use crate::theme::BaseColor;
+
+    #[test]
+    fn test_iter() {
+        let palette = Palette::default();
+        let roles: Vec<_> = palette.iter().map(|(role, _)| role).collect();
+
+        assert_eq!(
+            roles,
+            vec![
+                "background",
+                "shadow",
+                "view",
+                "primary",
+                "secondary",
+                "tertiary",
+                "title_primary",
+                "title_secondary",
+                "title_focused",
+                "highlight",
+                "highlight_inactive",
+                "highlight_text",
+                "row_even",
+                "row_odd",
+                "error",
+                "warning",
+                "success",
+            ]
+        );
+
+        for (role, color) in palette.iter() {
+            let role = PaletteColor::from_str(role).unwrap();
+            assert_eq!(palette[role], color);
+        }
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let palette = Palette::default();
+        let mut colors = Vec::new();
+        for color in &palette {
+            colors.push(color);
+        }
+
+        assert_eq!(colors.len(), 17);
+        assert_eq!((&palette).into_iter().len(), 17);
+        assert_eq!(colors[0], palette[PaletteColor::Background]);
+        assert!(colors
+            .into_iter()
+            .eq(palette.iter().map(|(_, color)| color)));
+    }
+
+    #[test]
+    fn test_hash_custom_order() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(palette: &Palette) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            palette.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut a = Palette::default();
+        let mut b = Palette::default();
+        for &(key, color) in &[
+            ("link", Color::Rgb(0, 0, 255)),
+            ("visited", Color::Rgb(128, 0, 128)),
+        ] {
+            a.set_color(key, color);
+        }
+        for &(key, color) in &[
+            ("visited", Color::Rgb(128, 0, 128)),
+            ("link", Color::Rgb(0, 0, 255)),
+        ] {
+            b.set_color(key, color);
+        }
+
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn test_contrast_report() {
+        let report = Palette::default().contrast_report();
+        assert_eq!(report.len(), 11);
+
+        let (label, ratio) = &report[0];
+        assert_eq!(label, "primary on view");
+        // Black on light gray should be easily readable.
+        assert!(*ratio > 7.0, "{}", ratio);
+
+        assert_eq!(report[6].0, "error on view");
+        assert_eq!(report[7].0, "warning on view");
+        assert_eq!(report[8].0, "success on view");
+        assert_eq!(report[10].0, "highlight_text on highlight_inactive");
+    }
+
+    #[test]
+    fn test_ensure_contrast() {
+        let mut palette = Palette::default();
+        palette[PaletteColor::View] = Color::Rgb(40, 40, 60);
+        palette[PaletteColor::Primary] = Color::Rgb(60, 60, 80);
+        palette[PaletteColor::Secondary] = Color::Rgb(20, 20, 20);
+        palette[PaletteColor::Error] = Color::Rgb(40, 40, 60);
+        let highlight = palette[PaletteColor::Highlight];
+
+        palette.ensure_contrast(4.5);
+        for (label, ratio) in palette.contrast_report() {
+            if label.ends_with(" on view") {
+                assert!(ratio >= 4.5, "{}: {}", label, ratio);
+            }
+        }
+        // Backgrounds are left alone.
+        assert_eq!(palette[PaletteColor::View], Color::Rgb(40, 40, 60));
+        assert_eq!(palette[PaletteColor::Highlight], highlight);
+
+        // Readable colors are not changed.
+        let mut palette = Palette::default();
+        palette.ensure_contrast(1.0);
+        assert_eq!(palette, Palette::default());
+    }
+
+    #[test]
+    fn test_fallback() {
+        let palette = Palette::default();
+        assert_eq!(
+            palette[PaletteColor::TitleFocused],
+            palette[PaletteColor::TitlePrimary]
+        );
+        assert_eq!(
+            PaletteColor::TitleFocused.fallback(),
+            Some(PaletteColor::TitlePrimary)
+        );
+        assert_eq!(PaletteColor::Primary.fallback(), None);
+    }
+
+    #[test]
+    fn test_fallbacks_agree() {
+        let mut palette = Palette::default();
+        palette.update_rows();
+
+        for (name, _) in Palette::default().iter() {
+            let role = PaletteColor::from_str(name).unwrap();
+            assert_eq!(
+                role.fallback(),
+                role.fallback_chain().first().copied()
+            );
+
+            // Resolving an unset role gives the color it gets when loading.
+            let mut unset = palette.clone();
+            unset[role] = Color::TerminalDefault;
+            let expected =
+                role.fallback().map_or(Color::TerminalDefault, |fallback| {
+                    role.inherit(palette[fallback])
+                });
+            assert_eq!(
+                role.resolve_with_fallbacks(&unset),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_fallback() {
+        let palette: Palette =
+            serde_json::from_str(r#"{"title_primary": "green"}"#).unwrap();
+        assert_eq!(
+            palette[PaletteColor::TitleFocused],
+            Color::Dark(BaseColor::Green)
+        );
+
+        let palette: Palette = serde_json::from_str(
+            r#"{"title_primary": "green", "title_focused": "blue"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            palette[PaletteColor::TitleFocused],
+            Color::Dark(BaseColor::Blue)
+        );
+    }
+
+    #[test]
+    fn test_map_colors() {
+        let mut palette = Palette::default();
+        palette.set_color("accent", Color::Rgb(100, 100, 100));
+
+        assert_eq!(palette.map_colors(|color| color), palette);
+
+        let darker = palette.map_colors(|color| color.darken(0.5));
+        for ((role, before), (_, after)) in palette.iter().zip(darker.iter()) {
+            assert_ne!(before, after, "{}", role);
+        }
+        assert_eq!(darker.custom("accent"), Some(&Color::Rgb(50, 50, 50)));
+    }
+
+    #[test]
+    fn test_blend_with() {
+        let mut dark = Palette::default();
+        dark[PaletteColor::View] = Color::Rgb(0, 0, 0);
+        dark.set_color("accent", Color::Rgb(0, 0, 0));
+        dark.set_color("only_dark", Color::Rgb(1, 1, 1));
+
+        let mut light = Palette::default();
+        light[PaletteColor::View] = Color::Rgb(200, 100, 50);
+        light.set_color("accent", Color::Rgb(100, 100, 100));
+
+        assert_eq!(dark.blend_with(&light, 0.0), dark);
+        assert_eq!(dark.blend_with(&light, 1.0), light);
+
+        let middle = dark.blend_with(&light, 0.5);
+        assert_eq!(middle[PaletteColor::View], Color::Rgb(100, 50, 25));
+        assert_eq!(middle.custom("accent"), Some(&Color::Rgb(50, 50, 50)));
+        assert_eq!(middle.custom("only_dark"), None);
+        // Roles identical in both palettes are unchanged.
+        assert_eq!(
+            middle[PaletteColor::Primary].to_rgb(),
+            dark[PaletteColor::Primary].to_rgb()
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Palette::default(), crate::theme::Theme::default().palette);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_custom() {
+        let palette: Palette = serde_json::from_str(
+            r##"{
+                "primary": "red",
+                "accent": ["nope", "light blue"],
+                "menu": { "view": "black" }
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            palette[PaletteColor::Primary],
+            Color::Dark(BaseColor::Red)
+        );
+        assert_eq!(
+            palette.custom("accent"),
+            Some(&Color::Light(BaseColor::Blue))
+        );
+        assert_eq!(
+            palette.merge("menu")[PaletteColor::View],
+            Color::Dark(BaseColor::Black)
+        );
+
+        let json = serde_json::to_string(&palette).unwrap();
+        assert_eq!(serde_json::from_str::<Palette>(&json).unwrap(), palette);
+
+        assert!(serde_json::from_str::<Palette>(r#"{"view": "rd"}"#).is_err());
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut palette = Palette::default();
+
+        assert!(palette.set("primary", Color::Rgb(0, 0, 0)));
+        assert_eq!(palette.get("primary"), Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(palette[PaletteColor::Primary], Color::Rgb(0, 0, 0));
+
+        assert!(!palette.set("foo", Color::Rgb(0, 0, 0)));
+        assert_eq!(palette.get("foo"), None);
+        assert_eq!(palette.custom("foo"), None);
+    }
+}
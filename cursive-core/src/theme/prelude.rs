@@ -0,0 +1,23 @@
+//! Re-exports the most common theme types and functions.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use cursive_core::theme::prelude::*;
+//!
+//! let mut theme = Theme {
+//!     borders: BorderStyle::Outset,
+//!     ..load_default()
+//! };
+//! theme.palette[PaletteColor::View] = Color::Dark(BaseColor::Black);
+//!
+//! assert_eq!(theme.palette[PaletteColor::View], BaseColor::Black.dark());
+//! ```
+
+pub use super::{
+    load_default, BaseColor, BorderStyle, Color, ColorStyle, Effect, Palette,
+    PaletteColor, Theme,
+};
+
+#[cfg(feature = "toml")]
+pub use super::{load_theme_file, load_toml};
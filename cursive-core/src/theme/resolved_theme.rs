@@ -0,0 +1,85 @@
+use super::{Color, ColorPair, ColorStyle, ColorType, PaletteColor, Theme};
+use enum_map::EnumMap;
+
+/// Colors of a theme, resolved ahead of time.
+///
+/// Resolving a [`ColorStyle`] made of palette colors is then a simple
+/// lookup, which can help when redrawing often.
+///
+/// It needs to be re-created whenever the theme changes.
+///
+/// [`ColorStyle`]: struct.ColorStyle.html
+#[derive(Clone, Debug)]
+pub struct ResolvedTheme {
+    // Pair for every (front, back) combination of palette colors.
+    pairs: EnumMap<PaletteColor, EnumMap<PaletteColor, ColorPair>>,
+}
+
+impl ResolvedTheme {
+    /// Resolves every palette color combination from `theme`.
+    pub fn new(theme: &Theme) -> Self {
+        let palette = &theme.palette;
+        let pairs = EnumMap::from(|front| {
+            EnumMap::from(|back| ColorPair {
+                front: palette[front],
+                back: palette[back],
+            })
+        });
+
+        ResolvedTheme { pairs }
+    }
+
+    /// Returns the color pair for the given style.
+    ///
+    /// This is the same as `style.resolve(&theme.palette)`.
+    pub fn pair(&self, style: ColorStyle) -> ColorPair {
+        match (style.front, style.back) {
+            (ColorType::Palette(front), ColorType::Palette(back)) => {
+                self.pairs[front][back]
+            }
+            (front, back) => ColorPair {
+                front: self.color(front),
+                back: self.color(back),
+            },
+        }
+    }
+
+    /// Returns the actual color for the given color type.
+    pub fn color(&self, color: ColorType) -> Color {
+        match color {
+            ColorType::Color(color) => color,
+            ColorType::Palette(color) => self.pairs[color][color].front,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::BaseColor;
+
+    #[test]
+    fn test_pair() {
+        let theme = Theme::from_accent(Color::Dark(BaseColor::Magenta));
+        let resolved = ResolvedTheme::new(&theme);
+
+        let styles = [
+            ColorStyle::terminal_default(),
+            ColorStyle::background(),
+            ColorStyle::shadow(),
+            ColorStyle::primary(),
+            ColorStyle::secondary(),
+            ColorStyle::tertiary(),
+            ColorStyle::title_primary(),
+            ColorStyle::title_secondary(),
+            ColorStyle::highlight(),
+            ColorStyle::highlight_inactive(),
+            ColorStyle::new(Color::Rgb(1, 2, 3), PaletteColor::View),
+            ColorStyle::new(PaletteColor::Primary, Color::TerminalDefault),
+        ];
+
+        for &style in &styles {
+            assert_eq!(resolved.pair(style), style.resolve(&theme.palette));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use super::{Color, Error, PaletteColor, Theme};
+use serde_json::Value;
+use std::collections::HashMap;
+
+// Names used by Windows Terminal for the 16 ANSI colors.
+const WINDOWS_TERMINAL_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+impl Theme {
+    /// Imports a terminal color scheme.
+    ///
+    /// Both iTerm2 schemes exported as JSON (with `"Ansi 0 Color"`,
+    /// `"Background Color"`, ... entries) and Windows Terminal schemes (with
+    /// `"black"`, `"brightBlack"`, `"background"`, ... entries) are
+    /// supported.
+    ///
+    /// Palette roles using a base color in the default theme use the
+    /// matching ANSI color from the scheme. `view` uses the scheme
+    /// background and `primary` its foreground. Colors missing from the
+    /// scheme keep their default value.
+    ///
+    /// Must have the `serde_json` feature enabled.
+    pub fn from_iterm_scheme(json: &str) -> Result<Theme, Error> {
+        let scheme: HashMap<String, Value> = serde_json::from_str(json)?;
+
+        let lookup = |iterm: &str, windows: &str| {
+            scheme.get(iterm).and_then(parse_iterm_color).or_else(|| {
+                scheme
+                    .get(windows)
+                    .and_then(Value::as_str)
+                    .and_then(Color::parse)
+            })
+        };
+
+        let ansi: Vec<Option<Color>> = WINDOWS_TERMINAL_NAMES
+            .iter()
+            .enumerate()
+            .map(|(i, name)| lookup(&format!("Ansi {} Color", i), name))
+            .collect();
+
+        let mut theme = Theme::default();
+        for (role, color) in Theme::default().palette.iter() {
+            let index = match color {
+                Color::Dark(base) => base as usize,
+                Color::Light(base) => base as usize + 8,
+                _ => continue,
+            };
+            if let Some(color) = ansi[index] {
+                theme.palette.set(role, color);
+            }
+        }
+
+        if let Some(color) = lookup("Background Color", "background") {
+            theme.palette[PaletteColor::View] = color;
+            theme.palette.update_rows();
+        }
+        if let Some(color) = lookup("Foreground Color", "foreground") {
+            theme.palette[PaletteColor::Primary] = color;
+        }
+
+        Ok(theme)
+    }
+}
+
+/// Parses an iTerm2 color, with components between 0 and 1.
+fn parse_iterm_color(value: &Value) -> Option<Color> {
+    let component = |name: &str| {
+        let value = value.get(name)?.as_f64()?;
+        Some((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+    };
+
+    Some(Color::Rgb(
+        component("Red Component")?,
+        component("Green Component")?,
+        component("Blue Component")?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iterm_scheme() {
+        let theme = Theme::from_iterm_scheme(
+            r#"{
+                "Ansi 1 Color": {
+                    "Red Component": 0.8,
+                    "Green Component": 0.2,
+                    "Blue Component": 0.2,
+                    "Color Space": "sRGB"
+                },
+                "Background Color": {
+                    "Red Component": 0.0,
+                    "Green Component": 0.0,
+                    "Blue Component": 0.0
+                },
+                "Foreground Color": {
+                    "Red Component": 1.0,
+                    "Green Component": 1.0,
+                    "Blue Component": 1.0
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let palette = &theme.palette;
+        assert_eq!(palette[PaletteColor::Highlight], Color::Rgb(204, 51, 51));
+        assert_eq!(palette[PaletteColor::View], Color::Rgb(0, 0, 0));
+        assert_eq!(palette[PaletteColor::Primary], Color::Rgb(255, 255, 255));
+        // Missing colors use the default.
+        assert_eq!(
+            palette[PaletteColor::Background],
+            Theme::default().palette[PaletteColor::Background]
+        );
+    }
+
+    #[test]
+    fn test_windows_terminal_scheme() {
+        let theme = Theme::from_iterm_scheme(
+            r##"{
+                "name": "Sample",
+                "background": "#1e1e1e",
+                "foreground": "#d4d4d4",
+                "blue": "#2472c8",
+                "brightBlue": "#3b8eea"
+            }"##,
+        )
+        .unwrap();
+
+        let palette = &theme.palette;
+        assert_eq!(palette[PaletteColor::View], Color::Rgb(0x1e, 0x1e, 0x1e));
+        assert_eq!(
+            palette[PaletteColor::Background],
+            Color::Rgb(0x24, 0x72, 0xc8)
+        );
+        assert_eq!(
+            palette[PaletteColor::TitleSecondary],
+            Color::Rgb(0x3b, 0x8e, 0xea)
+        );
+    }
+
+    #[test]
+    fn test_invalid_scheme() {
+        assert!(matches!(
+            Theme::from_iterm_scheme("[1, 2, 3]"),
+            Err(Error::Json(_))
+        ));
+    }
+}
@@ -94,3 +94,123 @@ impl From<ColorType> for Style {
         ColorStyle::from(color).into()
     }
 }
+
+/// Stack of nested styles.
+///
+/// Each frame sets a color style and some effects. The current color style
+/// is the one from the top frame, while effects from all frames are
+/// combined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleStack {
+    base: ColorStyle,
+    frames: Vec<(ColorStyle, EnumSet<Effect>)>,
+}
+
+impl Default for StyleStack {
+    fn default() -> Self {
+        Self::new(ColorStyle::primary())
+    }
+}
+
+impl StyleStack {
+    /// Creates a new empty stack.
+    ///
+    /// `base` is the color style used when no frame is on the stack.
+    pub fn new(base: ColorStyle) -> Self {
+        StyleStack {
+            base,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Pushes a new frame on the stack.
+    pub fn push(&mut self, color: ColorStyle, effects: EnumSet<Effect>) {
+        self.frames.push((color, effects));
+    }
+
+    /// Removes the top frame from the stack, and returns it.
+    ///
+    /// Returns `None` if the stack was empty.
+    pub fn pop(&mut self) -> Option<(ColorStyle, EnumSet<Effect>)> {
+        self.frames.pop()
+    }
+
+    /// Returns the current color style and effects.
+    ///
+    /// The color style comes from the top frame, and effects from every
+    /// frame are combined.
+    pub fn current(&self) -> (ColorStyle, EnumSet<Effect>) {
+        let color = self
+            .frames
+            .last()
+            .map(|&(color, _)| color)
+            .unwrap_or(self.base);
+        let effects = self
+            .frames
+            .iter()
+            .fold(EnumSet::new(), |effects, &(_, frame)| effects | frame);
+
+        (color, effects)
+    }
+
+    /// Returns the current style.
+    ///
+    /// This is the same as [`current`](#method.current), as a `Style`.
+    pub fn current_style(&self) -> Style {
+        let (color, effects) = self.current();
+        Style {
+            effects,
+            color: Some(color),
+        }
+    }
+
+    /// Returns the number of frames on the stack.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frame is on the stack.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_stack() {
+        let mut stack = StyleStack::default();
+        assert_eq!(stack.current(), (ColorStyle::primary(), EnumSet::new()));
+
+        stack.push(ColorStyle::title_primary(), enum_set!(Effect::Bold));
+        stack.push(ColorStyle::highlight(), enum_set!(Effect::Underline));
+        assert_eq!(stack.len(), 2);
+        assert_eq!(
+            stack.current(),
+            (
+                ColorStyle::highlight(),
+                enum_set!(Effect::Bold | Effect::Underline)
+            )
+        );
+
+        assert_eq!(
+            stack.pop(),
+            Some((ColorStyle::highlight(), enum_set!(Effect::Underline)))
+        );
+        assert_eq!(
+            stack.current(),
+            (ColorStyle::title_primary(), enum_set!(Effect::Bold))
+        );
+        assert_eq!(
+            stack.current_style(),
+            Style::from(ColorStyle::title_primary()).combine(Effect::Bold)
+        );
+
+        stack.pop();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.current(), (ColorStyle::primary(), EnumSet::new()));
+    }
+}
@@ -0,0 +1,104 @@
+use super::{load_theme_file, Error, Theme};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A theme loaded from a file, which can be reloaded when the file changes.
+///
+/// Must have the `toml` feature enabled.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use cursive_core::theme::ThemeFile;
+/// let mut file = ThemeFile::load("assets/style.toml").unwrap();
+///
+/// // Later, maybe on a timer:
+/// if file.reload_if_changed().unwrap() {
+///     let theme = file.theme().clone();
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ThemeFile {
+    path: PathBuf,
+    theme: Theme,
+    modified: SystemTime,
+}
+
+impl ThemeFile {
+    /// Loads a theme from the given file.
+    ///
+    /// The modification time of the file is kept to detect later changes.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let modified = fs::metadata(&path)?.modified()?;
+        let theme = load_theme_file(&path)?;
+
+        Ok(ThemeFile {
+            path,
+            theme,
+            modified,
+        })
+    }
+
+    /// Reloads the theme if the file was modified since it was last loaded.
+    ///
+    /// Returns `true` if the theme was reloaded. If the new content cannot be
+    /// loaded, an error is returned and the current theme is kept.
+    pub fn reload_if_changed(&mut self) -> Result<bool, Error> {
+        let modified = fs::metadata(&self.path)?.modified()?;
+        if modified == self.modified {
+            return Ok(false);
+        }
+
+        self.theme = load_theme_file(&self.path)?;
+        self.modified = modified;
+        Ok(true)
+    }
+
+    /// Returns the loaded theme.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Returns the modification time of the file when it was last loaded.
+    pub fn modified(&self) -> SystemTime {
+        self.modified
+    }
+
+    /// Returns the path to the theme file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn test_reload_if_changed() {
+        let path = env::temp_dir()
+            .join(format!("cursive-theme-file-{}.toml", process::id()));
+
+        fs::write(&path, "shadow = false").unwrap();
+        let mut file = ThemeFile::load(&path).unwrap();
+        assert!(!file.theme().shadow);
+        assert!(!file.reload_if_changed().unwrap());
+
+        // Pretend the file was loaded a long time ago, so the new content is
+        // picked up even if the filesystem has a coarse time resolution.
+        fs::write(&path, "shadow = true").unwrap();
+        file.modified = SystemTime::UNIX_EPOCH;
+
+        assert!(file.reload_if_changed().unwrap());
+        assert!(file.theme().shadow);
+        assert!(!file.reload_if_changed().unwrap());
+
+        fs::remove_file(&path).unwrap();
+        assert!(file.reload_if_changed().is_err());
+    }
+}
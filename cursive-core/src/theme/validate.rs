@@ -0,0 +1,351 @@
+use super::palette::TEXT_ROLES;
+use super::{Color, ColorDepth, PaletteColor, Theme};
+
+/// Minimum contrast ratio expected between text and its background.
+///
+/// This is the WCAG recommendation for normal text.
+const MIN_CONTRAST_RATIO: f32 = 4.5;
+
+/// Problem found in a theme by [`Theme::validate`].
+///
+/// [`Theme::validate`]: struct.Theme.html#method.validate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThemeIssue {
+    /// The color for `role` cannot be displayed with the target color depth.
+    ///
+    /// It would be replaced by a different color.
+    Unrepresentable {
+        /// Role using the color.
+        role: PaletteColor,
+        /// The color that cannot be displayed.
+        color: Color,
+    },
+
+    /// Text printed with `front` is hard to read on `back`.
+    LowContrast {
+        /// Role of the text color.
+        front: PaletteColor,
+        /// Role of the background color.
+        back: PaletteColor,
+        /// Contrast ratio between both colors.
+        ratio: f32,
+    },
+
+    /// The highlight color for `role` is the same as the view color.
+    ///
+    /// Highlighted items would not be visible.
+    HighlightMatchesView {
+        /// Either `Highlight` or `HighlightInactive`.
+        role: PaletteColor,
+    },
+}
+
+impl Theme {
+    /// Checks this theme for common problems.
+    ///
+    /// This reports:
+    ///
+    /// * Colors that cannot be displayed with the given `depth`.
+    /// * Text roles with a contrast ratio below 4.5 against their
+    ///   background (see [`Palette::contrast_report`]).
+    /// * Highlight colors identical to the view color.
+    ///
+    /// Returns an empty list if no problem was found.
+    ///
+    /// [`Palette::contrast_report`]: struct.Palette.html#method.contrast_report
+    pub fn validate(&self, depth: ColorDepth) -> Vec<ThemeIssue> {
+        let palette = &self.palette;
+        let mut issues = Vec::new();
+
+        for (role, color) in palette.iter() {
+            if !color.is_representable(depth) {
+                issues.push(ThemeIssue::Unrepresentable {
+                    role: role.parse().unwrap(),
+                    color,
+                });
+            }
+        }
+
+        for &(front, back) in &TEXT_ROLES {
+            let ratio = palette[front].contrast_ratio(palette[back]);
+            if ratio < MIN_CONTRAST_RATIO {
+                issues.push(ThemeIssue::LowContrast { front, back, ratio });
+            }
+        }
+
+        for &role in
+            &[PaletteColor::Highlight, PaletteColor::HighlightInactive]
+        {
+            if palette[role] == palette[PaletteColor::View] {
+                issues.push(ThemeIssue::HighlightMatchesView { role });
+            }
+        }
+
+        issues
+    }
+
+    /// Makes sure both highlight colors can be told apart.
+    ///
+    /// If the [distance] between `Highlight` and `HighlightInactive` is
+    /// below `min_distance`, `HighlightInactive` is gradually mixed with the
+    /// `Background` color until it is far enough. If that is not enough
+    /// (for example if the background is the same color), it is darkened
+    /// instead, or lightened for dark highlights.
+    ///
+    /// [distance]: enum.Color.html#method.distance
+    pub fn ensure_distinct_highlights(&mut self, min_distance: f32) {
+        let palette = &mut self.palette;
+        let highlight = palette[PaletteColor::Highlight];
+        let inactive = palette[PaletteColor::HighlightInactive];
+        if highlight.distance(inactive) >= min_distance {
+            return;
+        }
+
+        let background = palette[PaletteColor::Background];
+        let dark = highlight.luminance() < 0.179;
+        let steps = (1..=20).map(|step| step as f32 / 20.0);
+
+        let candidates = steps
+            .clone()
+            .map(|amount| inactive.mix(background, amount))
+            .chain(steps.map(|amount| {
+                if dark {
+                    inactive.lighten(amount)
+                } else {
+                    inactive.darken(amount)
+                }
+            }));
+
+        let mut best = inactive;
+        for candidate in candidates {
+            if candidate.distance(highlight) > best.distance(highlight) {
+                best = candidate;
+            }
+            if best.distance(highlight) >= min_distance {
+                break;
+            }
+        }
+        palette[PaletteColor::HighlightInactive] = best;
+    }
+
+    /// Sets the color for the given role name, after checking the role.
+    ///
+    /// Unlike direct access to `palette`, this returns an error message if
+    /// `role` is not a known [`PaletteColor`], leaving the theme unchanged.
+    ///
+    /// See [`set_readable_color`] to also check the contrast.
+    ///
+    /// [`PaletteColor`]: enum.PaletteColor.html
+    /// [`set_readable_color`]: #method.set_readable_color
+    pub fn set_color(
+        &mut self,
+        role: &str,
+        color: Color,
+    ) -> Result<(), String> {
+        let role = parse_role(role)?;
+        self.palette[role] = color;
+        Ok(())
+    }
+
+    /// Sets the color for the given role name, if text stays readable.
+    ///
+    /// In addition to the checks done by [`set_color`], this returns an
+    /// error message if the new color would give a text role a contrast
+    /// ratio below 4.5 against its background. The theme is only changed if
+    /// no error is returned.
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn set_readable_color(
+        &mut self,
+        role: &str,
+        color: Color,
+    ) -> Result<(), String> {
+        let role = parse_role(role)?;
+        let mut palette = self.palette.clone();
+        palette[role] = color;
+
+        for &(front, back) in &TEXT_ROLES {
+            if front != role && back != role {
+                continue;
+            }
+
+            let ratio = palette[front].contrast_ratio(palette[back]);
+            if ratio < MIN_CONTRAST_RATIO {
+                return Err(format!(
+                    "Contrast between `{}` and `{}` would be too low \
+                     ({:.2}).",
+                    front.as_str(),
+                    back.as_str(),
+                    ratio
+                ));
+            }
+        }
+
+        self.palette = palette;
+        Ok(())
+    }
+}
+
+// Parses a role name, like `set_color` accepts it.
+fn parse_role(role: &str) -> Result<PaletteColor, String> {
+    role.parse()
+        .map_err(|()| format!("Unknown palette role `{}`.", role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::BaseColor;
+
+    #[test]
+    fn test_low_contrast() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::Primary] = Color::Rgb(180, 180, 180);
+        theme.palette[PaletteColor::View] = Color::Rgb(200, 200, 200);
+
+        let issues = theme.validate(ColorDepth::TrueColor);
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            ThemeIssue::LowContrast {
+                front: PaletteColor::Primary,
+                back: PaletteColor::View,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_low_contrast_status_roles() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::View] = Color::Rgb(200, 0, 0);
+        theme.palette[PaletteColor::Error] = Color::Rgb(200, 0, 0);
+
+        let issues = theme.validate(ColorDepth::TrueColor);
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            ThemeIssue::LowContrast {
+                front: PaletteColor::Error,
+                back: PaletteColor::View,
+                ..
+            }
+        )));
+
+        assert!(theme
+            .set_readable_color("warning", Color::Rgb(210, 10, 10))
+            .is_err());
+    }
+
+    #[test]
+    fn test_unrepresentable() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::Primary] = Color::Rgb(1, 2, 3);
+        theme.palette[PaletteColor::Secondary] = Color::Light(BaseColor::Red);
+
+        let issues = theme.validate(ColorDepth::Basic8);
+        assert!(issues.contains(&ThemeIssue::Unrepresentable {
+            role: PaletteColor::Primary,
+            color: Color::Rgb(1, 2, 3),
+        }));
+        assert!(issues.contains(&ThemeIssue::Unrepresentable {
+            role: PaletteColor::Secondary,
+            color: Color::Light(BaseColor::Red),
+        }));
+
+        let issues = theme.validate(ColorDepth::TrueColor);
+        assert!(!issues
+            .iter()
+            .any(|issue| matches!(issue, ThemeIssue::Unrepresentable { .. })));
+    }
+
+    #[test]
+    fn test_ensure_distinct_highlights() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::Background] = Color::Rgb(0, 0, 64);
+        theme.palette[PaletteColor::Highlight] = Color::Rgb(200, 50, 50);
+        theme.palette[PaletteColor::HighlightInactive] =
+            Color::Rgb(200, 50, 50);
+
+        theme.ensure_distinct_highlights(100.0);
+        let inactive = theme.palette[PaletteColor::HighlightInactive];
+        assert!(inactive.distance(Color::Rgb(200, 50, 50)) >= 100.0);
+
+        // Already distinct highlights are unchanged.
+        let before = theme.palette.clone();
+        theme.ensure_distinct_highlights(100.0);
+        assert_eq!(theme.palette, before);
+    }
+
+    #[test]
+    fn test_ensure_distinct_highlights_same_background() {
+        let mut theme = Theme::default();
+        for &role in &[
+            PaletteColor::Background,
+            PaletteColor::Highlight,
+            PaletteColor::HighlightInactive,
+        ] {
+            theme.palette[role] = Color::Rgb(220, 220, 220);
+        }
+
+        theme.ensure_distinct_highlights(50.0);
+        let inactive = theme.palette[PaletteColor::HighlightInactive];
+        assert!(inactive.distance(Color::Rgb(220, 220, 220)) >= 50.0);
+    }
+
+    #[test]
+    fn test_set_color() {
+        let mut theme = Theme::default();
+        assert_eq!(theme.set_color("primary", Color::Rgb(1, 2, 3)), Ok(()));
+        assert_eq!(theme.palette[PaletteColor::Primary], Color::Rgb(1, 2, 3));
+
+        assert_eq!(
+            theme.set_color("not_a_role", Color::Rgb(1, 2, 3)),
+            Err("Unknown palette role `not_a_role`.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_readable_color() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::View] = Color::Rgb(255, 255, 255);
+
+        assert!(theme
+            .set_readable_color("primary", Color::Rgb(250, 250, 250))
+            .is_err());
+        assert_ne!(
+            theme.palette[PaletteColor::Primary],
+            Color::Rgb(250, 250, 250)
+        );
+
+        assert_eq!(
+            theme.set_readable_color("primary", Color::Rgb(0, 0, 0)),
+            Ok(())
+        );
+        assert_eq!(theme.palette[PaletteColor::Primary], Color::Rgb(0, 0, 0));
+
+        // CamelCase role names are checked too.
+        assert!(theme
+            .set_readable_color("Primary", Color::Rgb(250, 250, 250))
+            .is_err());
+        assert_eq!(theme.palette[PaletteColor::Primary], Color::Rgb(0, 0, 0));
+        assert!(theme
+            .set_readable_color("View", Color::Rgb(0, 0, 0))
+            .is_err());
+        assert_eq!(
+            theme.set_readable_color("not_a_role", Color::Rgb(0, 0, 0)),
+            Err("Unknown palette role `not_a_role`.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_highlight_matches_view() {
+        let mut theme = Theme::default();
+        theme.palette[PaletteColor::HighlightInactive] =
+            theme.palette[PaletteColor::View];
+
+        assert!(theme.validate(ColorDepth::TrueColor).contains(
+            &ThemeIssue::HighlightMatchesView {
+                role: PaletteColor::HighlightInactive
+            }
+        ));
+    }
+}
@@ -216,14 +216,27 @@ pub fn draw_frame<T, LeftBorder, TopBorder, RightBorder, BottomBorder>(
     }
 
     // Also draw padding
+    let chars = printer.theme.borders.frame_chars();
     let scrollbar_size = scroller.get_scroller().scrollbar_size();
-    printer.print_hline((viewport.right() + 2, 0), scrollbar_size.x, "─");
-    printer.print_hline((viewport.right() + 2, size.y), scrollbar_size.x, "─");
-    printer.print_vline((0, viewport.bottom() + 2), scrollbar_size.y, "│");
+    printer.print_hline(
+        (viewport.right() + 2, 0),
+        scrollbar_size.x,
+        chars.horizontal,
+    );
+    printer.print_hline(
+        (viewport.right() + 2, size.y),
+        scrollbar_size.x,
+        chars.horizontal,
+    );
+    printer.print_vline(
+        (0, viewport.bottom() + 2),
+        scrollbar_size.y,
+        chars.vertical,
+    );
     printer.print_vline(
         (size.x, viewport.bottom() + 2),
         scrollbar_size.y,
-        "│",
+        chars.vertical,
     );
 
     for (i, y) in (viewport.top()..=viewport.bottom()).enumerate() {
@@ -231,10 +244,10 @@ pub fn draw_frame<T, LeftBorder, TopBorder, RightBorder, BottomBorder>(
         right_border(scroller, &printer.offset((size.x, i + 1)), y);
     }
 
-    printer.print((0, 0), "┌");
-    printer.print(size.keep_y(), "└");
-    printer.print(size.keep_x(), "┐");
-    printer.print(size, "┘");
+    printer.print((0, 0), chars.top_left);
+    printer.print(size.keep_y(), chars.bottom_left);
+    printer.print(size.keep_x(), chars.top_right);
+    printer.print(size, chars.bottom_right);
 }
 
 /// Draws a box-style frame around a scrollable content.
@@ -253,35 +266,36 @@ pub fn draw_box_frame<T, IsHDelim, IsVDelim>(
     IsHDelim: Fn(&T, usize) -> bool,
     IsVDelim: Fn(&T, usize) -> bool,
 {
+    let chars = printer.theme.borders.frame_chars();
     draw_frame(
         scroller,
         printer,
         |s, printer, y| {
             if is_h_delim(s, y) {
-                printer.print((0, 0), "├");
+                printer.print((0, 0), chars.left_tee);
             } else {
-                printer.print((0, 0), "│");
+                printer.print((0, 0), chars.vertical);
             }
         },
         |s, printer, x| {
             if is_v_delim(s, x) {
-                printer.print((0, 0), "┬");
+                printer.print((0, 0), chars.top_tee);
             } else {
-                printer.print((0, 0), "─");
+                printer.print((0, 0), chars.horizontal);
             }
         },
         |s, printer, y| {
             if is_h_delim(s, y) {
-                printer.print((0, 0), "┤");
+                printer.print((0, 0), chars.right_tee);
             } else {
-                printer.print((0, 0), "│");
+                printer.print((0, 0), chars.vertical);
             }
         },
         |s, printer, x| {
             if is_v_delim(s, x) {
-                printer.print((0, 0), "┴");
+                printer.print((0, 0), chars.bottom_tee);
             } else {
-                printer.print((0, 0), "─");
+                printer.print((0, 0), chars.horizontal);
             }
         },
     );
@@ -340,7 +340,12 @@ impl View for MenuPopup {
                 match *item {
                     MenuItem::Delimiter => {
                         // printer.print_hdelim((0, 0), printer.size.x)
-                        printer.print_hline((0, 0), printer.size.x, "─");
+                        let chars = printer.theme.borders.frame_chars();
+                        printer.print_hline(
+                            (0, 0),
+                            printer.size.x,
+                            chars.horizontal,
+                        );
                     }
                     MenuItem::Subtree(ref label, _) => {
                         if printer.size.x < 4 {
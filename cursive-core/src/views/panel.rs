@@ -74,9 +74,10 @@ impl<V> Panel<V> {
                 + self
                     .title_position
                     .get_offset(len, printer.size.x - spacing_both_ends);
+            let chars = printer.theme.borders.chars();
             printer.with_high_border(false, |printer| {
-                printer.print((x - 2, 0), "┤ ");
-                printer.print((x + len, 0), " ├");
+                printer.print((x - 2, 0), &format!("{} ", chars.right_tee));
+                printer.print((x + len, 0), &format!(" {}", chars.left_tee));
             });
 
             printer.with_color(ColorStyle::title_primary(), |p| {
@@ -127,3 +128,27 @@ impl<V: View> ViewWrapper for Panel<V> {
         self.invalidated || self.view.needs_relayout()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Recorder;
+    use crate::theme::{BorderStyle, Theme};
+    use crate::views::DummyView;
+
+    #[test]
+    fn test_double_border_title() {
+        let theme = Theme {
+            borders: BorderStyle::Double,
+            ..Theme::default()
+        };
+        let backend = Recorder::default();
+        let printer = Printer::new((12, 5), &theme, &backend);
+
+        let mut view = Panel::new(DummyView).title("Hi");
+        view.layout((12, 5).into());
+        view.draw(&printer);
+
+        assert_eq!(backend.line(0, 12), "╔══╣ Hi ╠══╗");
+    }
+}
@@ -20,8 +20,7 @@ use crossterm::{
     },
     execute, queue,
     style::{
-        Attribute, Color, Print, SetAttribute, SetBackgroundColor,
-        SetForegroundColor,
+        Attribute, Print, SetAttribute, SetBackgroundColor, SetForegroundColor,
     },
     terminal::{
         self, disable_raw_mode, enable_raw_mode, Clear, ClearType,
@@ -149,42 +148,6 @@ fn translate_event(event: CKeyEvent) -> Event {
     }
 }
 
-fn translate_color(base_color: theme::Color) -> Color {
-    match base_color {
-        theme::Color::Dark(theme::BaseColor::Black) => Color::Black,
-        theme::Color::Dark(theme::BaseColor::Red) => Color::DarkRed,
-        theme::Color::Dark(theme::BaseColor::Green) => Color::DarkGreen,
-        theme::Color::Dark(theme::BaseColor::Yellow) => Color::DarkYellow,
-        theme::Color::Dark(theme::BaseColor::Blue) => Color::DarkBlue,
-        theme::Color::Dark(theme::BaseColor::Magenta) => Color::DarkMagenta,
-        theme::Color::Dark(theme::BaseColor::Cyan) => Color::DarkCyan,
-        theme::Color::Dark(theme::BaseColor::White) => Color::Grey,
-        theme::Color::Light(theme::BaseColor::Black) => Color::DarkGrey,
-        theme::Color::Light(theme::BaseColor::Red) => Color::Red,
-        theme::Color::Light(theme::BaseColor::Green) => Color::Green,
-        theme::Color::Light(theme::BaseColor::Yellow) => Color::Yellow,
-        theme::Color::Light(theme::BaseColor::Blue) => Color::Blue,
-        theme::Color::Light(theme::BaseColor::Magenta) => Color::Magenta,
-        theme::Color::Light(theme::BaseColor::Cyan) => Color::Cyan,
-        theme::Color::Light(theme::BaseColor::White) => Color::White,
-        theme::Color::Rgb(r, g, b) => Color::Rgb { r, g, b },
-        theme::Color::RgbLowRes(r, g, b) => {
-            debug_assert!(r <= 5,
-                              "Red color fragment (r = {}) is out of bound. Make sure r ≤ 5.",
-                              r);
-            debug_assert!(g <= 5,
-                              "Green color fragment (g = {}) is out of bound. Make sure g ≤ 5.",
-                              g);
-            debug_assert!(b <= 5,
-                              "Blue color fragment (b = {}) is out of bound. Make sure b ≤ 5.",
-                              b);
-
-            Color::AnsiValue(16 + 36 * r + 6 * g + b)
-        }
-        theme::Color::TerminalDefault => Color::Reset,
-    }
-}
-
 impl Backend {
     /// Creates a new crossterm backend.
     pub fn init() -> Result<Box<dyn backend::Backend>, crossterm::ErrorKind>
@@ -214,8 +177,8 @@ impl Backend {
     fn apply_colors(&self, colors: theme::ColorPair) {
         queue!(
             self.stdout_mut(),
-            SetForegroundColor(translate_color(colors.front)),
-            SetBackgroundColor(translate_color(colors.back))
+            SetForegroundColor(colors.front.into()),
+            SetBackgroundColor(colors.back.into())
         )
         .unwrap();
     }
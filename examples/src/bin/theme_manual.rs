@@ -22,7 +22,9 @@ fn main() {
                 theme.shadow = !theme.shadow;
                 theme.borders = match theme.borders {
                     BorderStyle::Simple => BorderStyle::Outset,
-                    BorderStyle::Outset => BorderStyle::None,
+                    BorderStyle::Outset => BorderStyle::Double,
+                    BorderStyle::Double => BorderStyle::Rounded,
+                    BorderStyle::Rounded => BorderStyle::None,
                     BorderStyle::None => BorderStyle::Simple,
                 };
 